@@ -0,0 +1,63 @@
+use std::{collections::HashMap, fs::File, io::BufReader};
+
+use semigroup::{layer::Layers, Semigroup};
+use serde::{
+    de::value::{self, MapDeserializer},
+    Deserialize,
+};
+
+#[derive(Debug, Clone, Deserialize, Semigroup)]
+#[semigroup(idempotent, with = "semigroup::op::Overwrite")]
+struct Person {
+    /// person name, required
+    name: String,
+
+    /// person age, required
+    age: u64,
+
+    /// person email, optional
+    #[semigroup(with = "semigroup::op::Coalesce")]
+    email: Option<String>,
+}
+
+/// Reading configs from multiple partial sources, merged by [`Layers`]
+///
+/// # Usage
+///  ```sh
+/// cargo run --example serde_layer --features serde -- semigroup/examples/john_doe.json
+/// cargo run --example serde_layer --features serde -- --name=alice --age=20
+/// NAME=bob cargo run --example serde_layer --features serde -- --age=20
+/// NAME=bob AGE=42 cargo run --example serde_layer --features serde -- semigroup/examples/john_doe.json --name=alice
+/// AGE=forty cargo run --example serde_layer --features serde -- semigroup/examples/john_doe.json
+/// ```
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (flags, files): (Vec<_>, Vec<_>) =
+        std::env::args().skip(1).partition(|a| a.starts_with("--"));
+    let cli: HashMap<_, _> = flags
+        .iter()
+        .filter_map(|f| f.trim_start_matches("--").split_once('='))
+        .collect();
+    let env: HashMap<_, _> = std::env::vars()
+        .filter(|(k, _)| ["NAME", "AGE", "EMAIL"].contains(&k.as_str()))
+        .map(|(k, v)| (k.to_lowercase(), v))
+        .collect();
+
+    let mut layers = Layers::new();
+    for file in files {
+        let reader = BufReader::new(File::open(&file)?);
+        layers = layers.layer(file, &mut serde_json::Deserializer::from_reader(reader))?;
+    }
+    let layers = layers
+        .layer(
+            "env",
+            MapDeserializer::<_, value::Error>::new(env.into_iter()),
+        )?
+        .layer(
+            "cli",
+            MapDeserializer::<_, value::Error>::new(cli.into_iter()),
+        )?;
+
+    let person: Person = layers.merge()?;
+    println!("{person:?}");
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use crate::{op::Overwrite, Commutative, Monoid, OptionMonoid};
+
+/// [`MonoidAction`] represents a [`Monoid`] acting on another [`Monoid`] `T`, that satisfies the following properties
+/// 1. *Identity*: `act(unit(), x) = x`
+/// 2. *Compatibility*: `act(op(f, g), x) = act(g, act(f, x))`
+/// 3. *Distributivity*: `act(f, op(x, y)) = op(act(f, x), act(f, y))`
+///
+/// `size` is the number of elements folded into `target`,
+/// so that an action over a range can be applied to its folded value at once.
+///
+/// # Examples
+/// Every [`Commutative`] [`Monoid`] acts on itself, such as range add on [`crate::op::Sum`].
+/// ```
+/// use semigroup::{op::Sum, MonoidAction};
+///
+/// // Sum(1) + Sum(2) + Sum(3), then add 10 to each element
+/// assert_eq!(Sum(10).act(Sum(6), 3), Sum(36));
+/// ```
+///
+/// [`OptionMonoid`] of [`Overwrite`] assigns a value to each element.
+/// ```
+/// use semigroup::{op::{Overwrite, Sum}, Monoid, MonoidAction, OptionMonoid};
+///
+/// // Sum(1) + Sum(2) + Sum(3), then assign 10 to each element
+/// assert_eq!(OptionMonoid::from(Overwrite(Sum(10))).act(Sum(6), 3), Sum(30));
+/// assert_eq!(OptionMonoid::<Overwrite<Sum<u32>>>::unit().act(Sum(6), 3), Sum(6));
+/// ```
+///
+/// # Testing
+/// Use [`crate::assert_monoid_action!`] macro.
+pub trait MonoidAction<T: Monoid>: Monoid {
+    fn act(&self, target: T, size: usize) -> T;
+}
+
+impl<T: Monoid + Commutative + Clone> MonoidAction<T> for T {
+    fn act(&self, target: T, size: usize) -> T {
+        T::op(target, repeat(self.clone(), size))
+    }
+}
+impl<T: Monoid + Clone> MonoidAction<T> for OptionMonoid<Overwrite<T>> {
+    fn act(&self, target: T, size: usize) -> T {
+        match self {
+            OptionMonoid(None) => target,
+            OptionMonoid(Some(Overwrite(value))) => repeat(value.clone(), size),
+        }
+    }
+}
+
+/// **O(log(n))**, fold `n` copies of `value` by doubling.
+fn repeat<T: Monoid + Clone>(value: T, n: usize) -> T {
+    let (mut result, mut base, mut n) = (T::unit(), value, n);
+    while n > 0 {
+        if n % 2 == 1 {
+            result = T::op(result, base.clone());
+        }
+        base = T::op(base.clone(), base);
+        n /= 2;
+    }
+    result
+}
+
+#[cfg(any(test, feature = "test"))]
+pub mod test_action {
+    use std::fmt::Debug;
+
+    use super::*;
+
+    /// Assert that the given action satisfies the *monoid action* property.
+    ///
+    /// # Usage
+    /// - 2 arguments: slice of more than 3 actions, and slice of more than 3 targets.
+    ///   2 actions and 2 targets are picked at random, seeded by [`crate::test_semigroup::SEED_ENV`] environment variable if set.
+    /// - 2 arguments and `seed = <u64>`: items are picked at random by the given seed.
+    /// - 2 arguments and `exhaustive`: all pairs of actions and pairs of targets are checked.
+    ///
+    /// # Examples
+    /// ```
+    /// use semigroup::{assert_monoid_action, op::Sum};
+    ///
+    /// assert_monoid_action!(&[Sum(1), Sum(2), Sum(3)], &[Sum(4), Sum(5), Sum(6)]);
+    /// assert_monoid_action!(&[Sum(1), Sum(2), Sum(3)], &[Sum(4), Sum(5), Sum(6)], seed = 42);
+    /// assert_monoid_action!(&[Sum(1), Sum(2), Sum(3)], &[Sum(4), Sum(5), Sum(6)], exhaustive);
+    /// ```
+    ///
+    /// # Panics
+    /// - If the given action does not satisfy the *monoid action* property.
+    ///   The seed and the indices of picked items are reported to stderr.
+    /// - The input iterator has less than 3 items.
+    #[macro_export]
+    macro_rules! assert_monoid_action {
+        ($f:expr, $x:expr, seed = $seed:expr) => {
+            $crate::assert_monoid_action!(@pick $f, $x, $crate::test_semigroup::Pick::Seed($seed))
+        };
+        ($f:expr, $x:expr, exhaustive) => {
+            $crate::assert_monoid_action!(@pick $f, $x, $crate::test_semigroup::Pick::Exhaustive)
+        };
+        (@pick $f:expr, $x:expr, $pick:expr) => {
+            $crate::test_semigroup::for_each_pick3($f, $pick, |f, g, _| {
+                $crate::test_semigroup::for_each_pick3($x, $pick, |x, y, _| {
+                    $crate::test_action::assert_monoid_action_impl(f.clone(), g.clone(), x, y);
+                })
+            })
+        };
+        ($f:expr, $x:expr) => {
+            $crate::assert_monoid_action!(@pick $f, $x, $crate::test_semigroup::Pick::Random)
+        };
+    }
+
+    pub fn assert_monoid_action_impl<F, T>(f: F, g: F, x: T, y: T)
+    where
+        F: MonoidAction<T> + Clone + Debug,
+        T: Monoid + Clone + PartialEq + Debug,
+    {
+        assert_eq!(F::unit().act(x.clone(), 1), x.clone());
+        assert_eq!(F::unit().act(y.clone(), 1), y.clone());
+        assert_eq!(
+            F::op(f.clone(), g.clone()).act(x.clone(), 1),
+            g.act(f.act(x.clone(), 1), 1)
+        );
+        assert_eq!(
+            F::op(g.clone(), f.clone()).act(y.clone(), 1),
+            f.act(g.act(y.clone(), 1), 1)
+        );
+        assert_eq!(
+            f.act(T::op(x.clone(), y.clone()), 2),
+            T::op(f.act(x.clone(), 1), f.act(y.clone(), 1))
+        );
+        assert_eq!(
+            g.act(T::op(x.clone(), T::op(y.clone(), x.clone())), 3),
+            T::op(g.act(T::op(x.clone(), y.clone()), 2), g.act(x.clone(), 1))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_monoid_action,
+        op::{Max, Min, Prod, Sum, Xor},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_repeat() {
+        assert_eq!(repeat(Sum(3), 0), Sum(0));
+        assert_eq!(repeat(Sum(3), 1), Sum(3));
+        assert_eq!(repeat(Sum(3), 10), Sum(30));
+        assert_eq!(repeat(Prod(2), 10), Prod(1024));
+        assert_eq!(repeat(Xor(0b101), 3), Xor(0b101));
+        assert_eq!(repeat(Xor(0b101), 4), Xor(0));
+    }
+
+    #[test]
+    fn test_commutative_monoid_action() {
+        assert_monoid_action!(&[Sum(1), Sum(2), Sum(3)], &[Sum(4), Sum(5), Sum(6)]);
+        assert_monoid_action!(&[Prod(1), Prod(2), Prod(3)], &[Prod(4), Prod(5), Prod(6)]);
+        assert_monoid_action!(&[Min(1), Min(5), Min(9)], &[Min(4), Min(5), Min(6)]);
+        assert_monoid_action!(&[Max(1), Max(5), Max(9)], &[Max(4), Max(5), Max(6)]);
+        assert_monoid_action!(&[Xor(1), Xor(2), Xor(3)], &[Xor(4), Xor(5), Xor(6)]);
+        assert_monoid_action!(
+            &[Sum(1), Sum(2), Sum(3)],
+            &[Sum(4), Sum(5), Sum(6)],
+            exhaustive
+        );
+        assert_monoid_action!(
+            &[Min(1), Min(5), Min(9)],
+            &[Min(4), Min(5), Min(6)],
+            seed = 42
+        );
+    }
+
+    #[test]
+    fn test_overwrite_monoid_action() {
+        fn assign<T: Monoid>(x: T) -> OptionMonoid<Overwrite<T>> {
+            OptionMonoid::from(Overwrite(x))
+        }
+        assert_monoid_action!(
+            &[OptionMonoid::unit(), assign(Sum(1)), assign(Sum(2))],
+            &[Sum(4), Sum(5), Sum(6)],
+            exhaustive
+        );
+        assert_monoid_action!(
+            &[OptionMonoid::unit(), assign(Min(1)), assign(Min(9))],
+            &[Min(4), Min(5), Min(6)]
+        );
+        assert_eq!(assign(Sum(3)).act(Sum(100), 4), Sum(12));
+        assert_eq!(assign(Max(3)).act(Max(100), 4), Max(3));
+    }
+}
@@ -66,6 +66,38 @@ use crate::{AnnotatedSemigroup, Semigroup};
 /// assert_eq!(ca.annotation().boolean, "first");
 /// assert_eq!(ca.annotation(), &ExampleStructAnnotation{ num: "third", str: "third", boolean: "first" });
 /// ```
+///
+/// ## provenance
+/// With `provenance` attribute, each field is annotated by [`crate::Provenance`],
+/// which records every contributing source, not only the winner.
+///
+/// ## nested
+/// With `nested` field attribute, the field of annotated derived type is annotated per its own fields.
+/// The annotation type of the field `Server` is `ServerAnnotation`, so it must be in the same scope.
+/// ```
+/// use semigroup::{Annotate, Semigroup};
+///
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(annotated, with = "semigroup::op::Coalesce")]
+/// struct Server {
+///     host: Option<String>,
+///     port: Option<u16>,
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(annotated, with = "semigroup::op::Coalesce")]
+/// struct Config {
+///     name: Option<String>,
+///     #[semigroup(nested)]
+///     server: Server,
+/// }
+///
+/// let file = Config { name: None, server: Server { host: None, port: Some(80) } }.annotated("file");
+/// let env = Config { name: None, server: Server { host: Some("localhost".into()), port: None } }.annotated("env");
+///
+/// let config = file.semigroup(env);
+/// assert_eq!(config.annotation().server, ServerAnnotation { host: "env", port: "file" });
+/// ```
 pub trait Annotate<A>: Sized {
     type Annotation;
     fn annotated(self, annotation: Self::Annotation) -> Annotated<Self, A>;
@@ -85,6 +117,7 @@ pub trait Annotate<A>: Sized {
 /// assert_eq!(annotated, Annotated::new(Coalesce(Some(1)), "first"));
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Annotated<T, A> {
     value: T,
     annotation: A,
@@ -297,4 +330,14 @@ pub mod tests {
         );
         assert_eq!(one_value_ref_mut_annotation_ref_mut.cloned(), annotated_one);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let annotated = Annotated::new(1, "first");
+        let json = serde_json::to_string(&annotated).unwrap();
+        assert_eq!(json, r#"{"value":1,"annotation":"first"}"#);
+        let deserialized: Annotated<i32, &str> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, annotated);
+    }
 }
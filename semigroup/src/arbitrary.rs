@@ -0,0 +1,377 @@
+#[cfg(feature = "proptest")]
+mod proptest_arbitrary {
+    use std::fmt::Debug;
+
+    use proptest::{
+        arbitrary::{any_with, Arbitrary},
+        strategy::{Map, Strategy},
+    };
+
+    use crate::{
+        op::{
+            Coalesce, CoalesceBy, CoalesceLast, CoalesceNonEmpty, CoalescePredicate, Concat,
+            IsEmpty, Max, MergeMap, MergeableMap, Min, Overwrite, Prod, Sum, Unique, Xor,
+        },
+        Annotated, Reverse, Semigroup,
+    };
+
+    macro_rules! impl_arbitrary {
+        ($ty:ident, $inner:ty, $($bound:tt)+) => {
+            impl<T: $($bound)+> Arbitrary for $ty<T>
+            where
+                $inner: Arbitrary,
+            {
+                type Parameters = <$inner as Arbitrary>::Parameters;
+                type Strategy = Map<<$inner as Arbitrary>::Strategy, fn($inner) -> Self>;
+                fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+                    any_with::<$inner>(args).prop_map($ty)
+                }
+            }
+        };
+    }
+
+    impl_arbitrary!(Coalesce, Option<T>, Debug);
+    impl_arbitrary!(CoalesceLast, Option<T>, Debug);
+    impl_arbitrary!(CoalesceNonEmpty, Option<T>, Debug + IsEmpty);
+    impl_arbitrary!(Concat, T, Debug + IntoIterator + FromIterator<T::Item>);
+    impl_arbitrary!(Max, T, Debug + Ord);
+    impl_arbitrary!(Min, T, Debug + Ord);
+    impl_arbitrary!(Overwrite, T, Debug);
+    impl_arbitrary!(Prod, T, Debug + std::ops::Mul<Output = T>);
+    impl_arbitrary!(Sum, T, Debug + std::ops::Add<Output = T>);
+    impl_arbitrary!(Xor, T, Debug + std::ops::BitXor<Output = T>);
+    impl_arbitrary!(Reverse, T, Debug);
+    impl_arbitrary!(Unique, Option<T>, Debug);
+
+    #[cfg(feature = "monoid")]
+    mod monoid {
+        use std::ops::Range;
+
+        use num::{Float, Integer, Unsigned};
+        use proptest::collection::{vec, VecStrategy};
+
+        use crate::{
+            op::{Gcd, Lcm, Moments},
+            OptionMonoid,
+        };
+
+        use super::*;
+
+        impl_arbitrary!(Gcd, T, Debug + Unsigned + Integer + Clone);
+        impl_arbitrary!(Lcm, T, Debug + Unsigned + Integer + Clone);
+        impl_arbitrary!(OptionMonoid, Option<T>, Debug + Semigroup);
+
+        impl<F: Float + Debug> Arbitrary for Moments<F> {
+            type Parameters = ();
+            type Strategy = Map<VecStrategy<Range<f64>>, fn(Vec<f64>) -> Self>;
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                vec(-1e3..1e3, 0..64).prop_map(|v| v.into_iter().filter_map(F::from).collect())
+            }
+        }
+    }
+
+    #[cfg(feature = "histogram")]
+    mod histogram {
+        use std::ops::Range;
+
+        use hdrhistogram::Counter;
+        use proptest::collection::{vec, VecStrategy};
+
+        use crate::op::HdrHistogram;
+
+        use super::*;
+
+        impl<T: Counter + Debug, const SIGFIG: u8, const LOW: u64, const HIGH: u64> Arbitrary
+            for HdrHistogram<T, SIGFIG, LOW, HIGH>
+        {
+            type Parameters = ();
+            type Strategy = Map<VecStrategy<Range<u64>>, fn(Vec<u64>) -> Self>;
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                vec(0..1 << 32, 0..32).prop_map(HdrHistogram::from_iter)
+            }
+        }
+    }
+
+    #[cfg(feature = "sketch")]
+    mod sketch {
+        use std::ops::Range;
+
+        use proptest::collection::{vec, VecStrategy};
+
+        use crate::op::{DDSketch, TDigest};
+
+        use super::*;
+
+        impl<const COMPRESSION: usize> Arbitrary for TDigest<COMPRESSION> {
+            type Parameters = ();
+            type Strategy = Map<VecStrategy<Range<f64>>, fn(Vec<f64>) -> Self>;
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                vec(-1e6..1e6, 0..64).prop_map(TDigest::from_iter)
+            }
+        }
+        impl<const ACCURACY_PERMILLE: u32> Arbitrary for DDSketch<ACCURACY_PERMILLE> {
+            type Parameters = ();
+            type Strategy = Map<VecStrategy<Range<f64>>, fn(Vec<f64>) -> Self>;
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                vec(-1e6..1e6, 0..64).prop_map(DDSketch::from_iter)
+            }
+        }
+    }
+
+    impl<T: Debug, P: CoalescePredicate<T>> Arbitrary for CoalesceBy<T, P>
+    where
+        Option<T>: Arbitrary,
+    {
+        type Parameters = <Option<T> as Arbitrary>::Parameters;
+        type Strategy = Map<<Option<T> as Arbitrary>::Strategy, fn(Option<T>) -> Self>;
+        fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+            any_with::<Option<T>>(args).prop_map(CoalesceBy::new)
+        }
+    }
+
+    impl<M: Debug + MergeableMap + Arbitrary> Arbitrary for MergeMap<M>
+    where
+        M::Value: Semigroup,
+    {
+        type Parameters = M::Parameters;
+        type Strategy = Map<M::Strategy, fn(M) -> Self>;
+        fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+            any_with::<M>(args).prop_map(MergeMap)
+        }
+    }
+
+    impl<T: Arbitrary, A: Arbitrary> Arbitrary for Annotated<T, A> {
+        type Parameters = (T::Parameters, A::Parameters);
+        type Strategy = Map<(T::Strategy, A::Strategy), fn((T, A)) -> Self>;
+        fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+            any_with::<(T, A)>(args).prop_map(|(value, annotation)| Self::new(value, annotation))
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_arbitrary {
+    use quickcheck::{Arbitrary, Gen};
+
+    use crate::{
+        op::{
+            Coalesce, CoalesceBy, CoalesceLast, CoalesceNonEmpty, CoalescePredicate, Concat,
+            IsEmpty, Max, MergeMap, MergeableMap, Min, Overwrite, Prod, Sum, Unique, Xor,
+        },
+        Annotated, Reverse, Semigroup,
+    };
+
+    macro_rules! impl_arbitrary {
+        ($ty:ident, $inner:ty, $($bound:tt)+) => {
+            impl<T: $($bound)+> Arbitrary for $ty<T>
+            where
+                $inner: Arbitrary,
+            {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    $ty(Arbitrary::arbitrary(g))
+                }
+                fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                    Box::new(self.0.shrink().map($ty))
+                }
+            }
+        };
+    }
+
+    impl_arbitrary!(Coalesce, Option<T>, Clone + 'static);
+    impl_arbitrary!(CoalesceLast, Option<T>, Clone + 'static);
+    impl_arbitrary!(CoalesceNonEmpty, Option<T>, Clone + 'static + IsEmpty);
+    impl_arbitrary!(
+        Concat,
+        T,
+        Clone + 'static + IntoIterator + FromIterator<T::Item>
+    );
+    impl_arbitrary!(Max, T, Clone + 'static + Ord);
+    impl_arbitrary!(Min, T, Clone + 'static + Ord);
+    impl_arbitrary!(Overwrite, T, Clone + 'static);
+    impl_arbitrary!(Prod, T, Clone + 'static + std::ops::Mul<Output = T>);
+    impl_arbitrary!(Sum, T, Clone + 'static + std::ops::Add<Output = T>);
+    impl_arbitrary!(Xor, T, Clone + 'static + std::ops::BitXor<Output = T>);
+    impl_arbitrary!(Reverse, T, Clone + 'static);
+    impl_arbitrary!(Unique, Option<T>, Clone + 'static);
+
+    #[cfg(feature = "monoid")]
+    mod monoid {
+        use num::{Float, Integer, Unsigned};
+
+        use crate::{
+            op::{Gcd, Lcm, Moments},
+            OptionMonoid,
+        };
+
+        use super::*;
+
+        impl_arbitrary!(Gcd, T, Clone + 'static + Unsigned + Integer);
+        impl_arbitrary!(Lcm, T, Clone + 'static + Unsigned + Integer);
+        impl_arbitrary!(OptionMonoid, Option<T>, Clone + 'static + Semigroup);
+
+        impl<F: Float + 'static> Arbitrary for Moments<F> {
+            fn arbitrary(g: &mut Gen) -> Self {
+                Vec::<i16>::arbitrary(g)
+                    .into_iter()
+                    .filter_map(F::from)
+                    .collect()
+            }
+        }
+    }
+
+    #[cfg(feature = "histogram")]
+    mod histogram {
+        use hdrhistogram::Counter;
+
+        use crate::op::HdrHistogram;
+
+        use super::*;
+
+        impl<T: Counter + 'static, const SIGFIG: u8, const LOW: u64, const HIGH: u64> Arbitrary
+            for HdrHistogram<T, SIGFIG, LOW, HIGH>
+        {
+            fn arbitrary(g: &mut Gen) -> Self {
+                Vec::<u32>::arbitrary(g)
+                    .into_iter()
+                    .map(u64::from)
+                    .collect()
+            }
+        }
+    }
+
+    #[cfg(feature = "sketch")]
+    mod sketch {
+        use crate::op::{DDSketch, TDigest};
+
+        use super::*;
+
+        impl<const COMPRESSION: usize> Arbitrary for TDigest<COMPRESSION> {
+            fn arbitrary(g: &mut Gen) -> Self {
+                Vec::<f64>::arbitrary(g).into_iter().collect()
+            }
+        }
+        impl<const ACCURACY_PERMILLE: u32> Arbitrary for DDSketch<ACCURACY_PERMILLE> {
+            fn arbitrary(g: &mut Gen) -> Self {
+                Vec::<f64>::arbitrary(g).into_iter().collect()
+            }
+        }
+    }
+
+    impl<T: Arbitrary, P: 'static + CoalescePredicate<T>> Arbitrary for CoalesceBy<T, P> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            CoalesceBy::new(Arbitrary::arbitrary(g))
+        }
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new(self.0.shrink().map(CoalesceBy::new))
+        }
+    }
+
+    impl<M: Clone + MergeableMap + Arbitrary> Arbitrary for MergeMap<M>
+    where
+        M::Value: Semigroup,
+    {
+        fn arbitrary(g: &mut Gen) -> Self {
+            MergeMap(M::arbitrary(g))
+        }
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new(self.0.shrink().map(MergeMap))
+        }
+    }
+
+    impl<T: Arbitrary, A: Arbitrary> Arbitrary for Annotated<T, A> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self::new(T::arbitrary(g), A::arbitrary(g))
+        }
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            let (value, annotation) = self.clone().into_parts();
+            Box::new(
+                (value, annotation)
+                    .shrink()
+                    .map(|(value, annotation)| Self::new(value, annotation)),
+            )
+        }
+    }
+}
+
+/// Property-based law checking with [`proptest`].
+///
+/// Every [`crate::op`] type, [`crate::Annotated`] and [`crate::OptionMonoid`] implements [`proptest::arbitrary::Arbitrary`],
+/// and [`quickcheck::Arbitrary`] with the `quickcheck` feature.
+///
+/// Failing inputs are shrunk, and the run is reproducible by `PROPTEST_RNG_SEED` environment variable.
+#[cfg(all(any(test, feature = "test"), feature = "proptest"))]
+pub mod test_proptest {
+    #[doc(hidden)]
+    pub use proptest;
+
+    /// Generate a property test that asserts the given type satisfies the *semigroup* property.
+    ///
+    /// # Usage
+    /// - 2 arguments: test name and type that implements [`proptest::arbitrary::Arbitrary`].
+    /// - 3 arguments: test name, type and [`proptest::strategy::Strategy`] of the type.
+    ///
+    /// The test name can be preceded by attributes such as `#[should_panic]`,
+    /// and by `#![proptest_config(..)]` to configure the runner.
+    ///
+    /// # Examples
+    /// ```
+    /// use proptest::prelude::*;
+    /// use semigroup::op::{Coalesce, Sum};
+    ///
+    /// semigroup::semigroup_laws!(coalesce_laws, Coalesce<u8>);
+    /// semigroup::semigroup_laws!(sum_laws, Sum<i64>, (-1000i64..1000).prop_map(Sum));
+    /// ```
+    #[macro_export]
+    macro_rules! semigroup_laws {
+        ($(#![proptest_config($config:expr)])? $(#[$meta:meta])* $name:ident, $ty:ty) => {
+            $crate::semigroup_laws!(
+                $(#![proptest_config($config)])? $(#[$meta])* $name,
+                $ty,
+                $crate::test_proptest::proptest::prelude::any::<$ty>()
+            );
+        };
+        ($(#![proptest_config($config:expr)])? $(#[$meta:meta])* $name:ident, $ty:ty, $strategy:expr) => {
+            $crate::test_proptest::proptest::proptest! {
+                $(#![proptest_config($config)])?
+                #[test]
+                $(#[$meta])*
+                fn $name((a, b, c) in ($strategy, $strategy, $strategy)) {
+                    $crate::test_semigroup::assert_semigroup_impl::<$ty>(a, b, c);
+                }
+            }
+        };
+    }
+
+    /// Generate a property test that asserts the given type satisfies the *monoid* property.
+    ///
+    /// # Usage
+    /// Same to [`crate::semigroup_laws!`].
+    ///
+    /// # Examples
+    /// ```
+    /// use semigroup::{op::Concat, OptionMonoid};
+    ///
+    /// semigroup::monoid_laws!(concat_laws, Concat<Vec<u8>>);
+    /// semigroup::monoid_laws!(option_monoid_laws, OptionMonoid<Concat<Vec<char>>>);
+    /// ```
+    #[macro_export]
+    macro_rules! monoid_laws {
+        ($(#![proptest_config($config:expr)])? $(#[$meta:meta])* $name:ident, $ty:ty) => {
+            $crate::monoid_laws!(
+                $(#![proptest_config($config)])? $(#[$meta])* $name,
+                $ty,
+                $crate::test_proptest::proptest::prelude::any::<$ty>()
+            );
+        };
+        ($(#![proptest_config($config:expr)])? $(#[$meta:meta])* $name:ident, $ty:ty, $strategy:expr) => {
+            $crate::test_proptest::proptest::proptest! {
+                $(#![proptest_config($config)])?
+                #[test]
+                $(#[$meta])*
+                fn $name((a, b, c) in ($strategy, $strategy, $strategy)) {
+                    $crate::test_monoid::assert_monoid_impl::<$ty>(a, b, c);
+                }
+            }
+        };
+    }
+}
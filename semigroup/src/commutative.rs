@@ -29,6 +29,20 @@ use crate::Semigroup;
 /// assert_eq!(a.semigroup(b).semigroup(c), ExampleStruct { sum: 111, min: 1 });
 /// ```
 ///
+/// The derive requires every field to be operated by a [`Commutative`] operation, so non-commutative fields cannot be derived.
+/// `skip` and `op` attributes are not supported, because their commutativity cannot be verified.
+/// ```compile_fail
+/// use semigroup::Semigroup;
+/// #[derive(Debug, Clone, PartialEq, Default, Semigroup)]
+/// #[semigroup(commutative)]
+/// pub struct ExampleStruct {
+///     #[semigroup(with = "semigroup::op::Sum")]
+///     pub sum: u32,
+///     #[semigroup(with = "semigroup::op::Overwrite")]
+///     pub last: u32,
+/// }
+/// ```
+///
 /// # Testing
 /// Use [`crate::assert_commutative!`] macro.
 /// This is marker trait.
@@ -58,6 +72,17 @@ pub mod test_commutative {
 
     #[macro_export]
     macro_rules! assert_commutative {
+        ($v:expr, seed = $seed:expr) => {
+            $crate::assert_commutative!(@pick $v, $crate::test_semigroup::Pick::Seed($seed))
+        };
+        ($v:expr, exhaustive) => {
+            $crate::assert_commutative!(@pick $v, $crate::test_semigroup::Pick::Exhaustive)
+        };
+        (@pick $v:expr, $pick:expr) => {
+            $crate::test_semigroup::for_each_pick3($v, $pick, |a, b, c| {
+                $crate::test_commutative::assert_commutative_impl(a.clone(), b.clone(), c.clone());
+            })
+        };
         ($a:expr, $b: expr, $($tail: expr),*) => {
             {
                 let v = vec![$a, $b, $($tail),*];
@@ -65,10 +90,7 @@ pub mod test_commutative {
             }
         };
         ($v:expr) => {
-            {
-                let (a, b, c) = $crate::test_semigroup::pick3($v);
-                $crate::test_commutative::assert_commutative_impl(a.clone(), b.clone(), c.clone());
-            }
+            $crate::assert_commutative!(@pick $v, $crate::test_semigroup::Pick::Random)
         };
     }
 
@@ -0,0 +1,230 @@
+use std::ops::{Range, RangeBounds};
+
+use crate::{range::indices, Commutative, Group, Monoid};
+
+/// [`FenwickTree`] (binary indexed tree) is a data structure for prefix queries with point updates.
+/// It requires the underlying operation on the data to form a [`Monoid`], and [`Group`] for arbitrary range queries.
+///
+/// Compared with [`crate::segment_tree::SegmentTree`], it uses half of the memory, but point updates require [`Commutative`].
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::Sum, fenwick_tree::FenwickTree};
+/// let data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+/// let mut sum_tree: FenwickTree<_> = data.into_iter().map(Sum).collect();
+/// assert_eq!(sum_tree.fold(3..=5).0, 12);
+/// sum_tree.update(4, Sum(8));
+/// assert_eq!(sum_tree.fold(3..=5).0, 16);
+/// sum_tree.operate(5, Sum(10));
+/// assert_eq!(sum_tree.fold(3..=5).0, 26);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FenwickTree<T> {
+    tree: Vec<T>, // 1-indexed, tree[i] = fold(i - lsb(i)..i), tree[0] is unused unit
+}
+impl<T: Monoid + Clone> FromIterator<T> for FenwickTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut fenwick_tree = Self::new();
+        fenwick_tree.extend(iter);
+        fenwick_tree
+    }
+}
+impl<T: Monoid + Clone> From<Vec<T>> for FenwickTree<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self::from_iter(v)
+    }
+}
+impl<T: Monoid + Clone> Default for FenwickTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> FenwickTree<T> {
+    /// **O(1)**, get the least significant bit, that is the number of data covered by the node.
+    #[inline]
+    fn lsb(node: usize) -> usize {
+        node & node.wrapping_neg()
+    }
+    /// **O(1)**, return this fenwick tree's number of data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tree.len() - 1
+    }
+    /// **O(1)**, check if this fenwick tree is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+impl<T: Monoid + Clone> FenwickTree<T> {
+    /// **O(1)**, init empty fenwick tree.
+    pub fn new() -> Self {
+        let tree = vec![T::unit()];
+        Self { tree }
+    }
+    /// amortized **O(1)**, push `x` to the fenwick tree.
+    pub fn push(&mut self, x: T) {
+        let node = self.len() + 1;
+        let mut res = x;
+        let mut size = 1;
+        while size < Self::lsb(node) {
+            res = T::op(self.tree[node - size].clone(), res);
+            size *= 2;
+        }
+        self.tree.push(res);
+    }
+
+    /// **O(log(n))**, fold the prefix `..end`.
+    pub fn prefix_fold(&self, end: usize) -> T {
+        let mut node = end.min(self.len());
+        let mut res = T::unit();
+        while node > 0 {
+            res = T::op(self.tree[node].clone(), res);
+            node -= Self::lsb(node);
+        }
+        res
+    }
+}
+impl<T: Monoid + Commutative + Clone> FenwickTree<T> {
+    /// **O(log(n))**, set `leaf[i] = op(leaf[i], x)`, and update fenwick tree.
+    ///
+    /// # Panics
+    /// - If `i` is greater than or equal to [`Self::len`].
+    pub fn operate(&mut self, i: usize, x: T) {
+        assert!(
+            i < self.len(),
+            "index {i} out of range for fenwick tree of length {}",
+            self.len()
+        );
+        let mut node = i + 1;
+        while node <= self.len() {
+            self.tree[node] = T::op(self.tree[node].clone(), x.clone());
+            node += Self::lsb(node);
+        }
+    }
+}
+impl<T: Group + Clone> FenwickTree<T> {
+    /// **O(log(n))**, fold the range by `op(inverse(fold(..start)), fold(..end))`.
+    pub fn fold<R>(&self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = indices(range, self.len());
+        if start >= end {
+            return T::unit();
+        }
+        T::op(self.prefix_fold(start).inverse(), self.prefix_fold(end))
+    }
+}
+impl<T: Group + Commutative + Clone> FenwickTree<T> {
+    /// **O(log(n))**, set `leaf[i] = x`, and update fenwick tree.
+    pub fn update(&mut self, i: usize, x: T) -> Option<T> {
+        self.update_with(i, |_| x)
+    }
+    /// **O(log(n))**, update `leaf[i]` by `f(leaf[i])`, and update fenwick tree.
+    pub fn update_with<F>(&mut self, i: usize, f: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> T,
+    {
+        (i < self.len()).then(|| {
+            let result = self.fold(i..=i);
+            let x = f(&result);
+            self.operate(i, T::op(result.clone().inverse(), x));
+            result
+        })
+    }
+}
+impl<T: Monoid + Clone> Extend<T> for FenwickTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iterator = iter.into_iter();
+        self.tree.reserve(iterator.size_hint().0);
+        iterator.for_each(|x| self.push(x));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        op::{Coalesce, Max, Overwrite, Sum, Xor},
+        OptionMonoid,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let mut data = [2, -5, 122, -33, -12, 14, -55, 500, 3];
+        let mut sum_tree = FenwickTree::from(data.map(Sum).to_vec());
+        assert_eq!(sum_tree, data.into_iter().map(Sum).collect());
+        for (i, x) in [(3, 10), (0, -1), (8, 0), (4, 7)] {
+            assert_eq!(sum_tree.update(i, Sum(x)), Some(Sum(data[i])));
+            data[i] = x;
+            for l in 0..=data.len() {
+                for r in l..=data.len() {
+                    assert_eq!(sum_tree.fold(l..r).0, data[l..r].iter().sum::<i32>());
+                }
+            }
+        }
+        assert_eq!(sum_tree.update(9, Sum(1)), None);
+        sum_tree.update_with(2, |&Sum(x)| Sum(x * 2));
+        assert_eq!(sum_tree.fold(2..3).0, 244);
+    }
+
+    #[test]
+    fn test_xor() {
+        let mut xor_tree: FenwickTree<_> =
+            [0b1010, 0b0110, 0b1111, 0b0001].map(Xor).to_vec().into();
+        assert_eq!(xor_tree.fold(..).0, 0b0010);
+        assert_eq!(xor_tree.fold(1..3).0, 0b1001);
+        xor_tree.operate(1, Xor(0b0110));
+        assert_eq!(xor_tree.fold(1..3).0, 0b1111);
+    }
+
+    #[test]
+    fn test_max() {
+        let mut max_tree: FenwickTree<_> = [2, -5, 122, -33, -12, 14].map(Max).to_vec().into();
+        assert_eq!(max_tree.prefix_fold(2).0, 2);
+        assert_eq!(max_tree.prefix_fold(6).0, 122);
+        max_tree.operate(1, Max(10));
+        assert_eq!(max_tree.prefix_fold(2).0, 10);
+        max_tree.push(Max(500));
+        assert_eq!(max_tree.prefix_fold(6).0, 122);
+        assert_eq!(max_tree.prefix_fold(7).0, 500);
+    }
+
+    #[test]
+    fn test_non_commutative_prefix_fold() {
+        let data = ["a", "b", "c", "d", "e", "f", "g"];
+        let coalesce_tree: FenwickTree<_> = data
+            .map(|s| OptionMonoid::from(Coalesce(Some(s))))
+            .to_vec()
+            .into();
+        let overwrite_tree: FenwickTree<_> = data
+            .into_iter()
+            .map(|s| OptionMonoid::from(Overwrite(s)))
+            .collect();
+        for (i, s) in data.into_iter().enumerate() {
+            assert_eq!(
+                coalesce_tree.prefix_fold(i + 1),
+                OptionMonoid::from(Coalesce(Some("a")))
+            );
+            assert_eq!(
+                overwrite_tree.prefix_fold(i + 1),
+                OptionMonoid::from(Overwrite(s))
+            );
+        }
+        assert_eq!(
+            overwrite_tree,
+            FenwickTree::from(data.map(|s| OptionMonoid::from(Overwrite(s))).to_vec())
+        );
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let empty = FenwickTree::<Sum<i64>>::from(vec![]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.fold(..), Sum(0));
+        assert_eq!(empty.prefix_fold(0), Sum(0));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::Monoid;
+
+/// [`Group`] represents a binary operation that satisfies the following properties
+/// 1. *Closure*: `op: T × T → T`
+/// 2. *Associativity*: `op(op(a, b), c) = op(a, op(b, c))`
+/// 3. Existence of *identity element*: `op(unit(), a) = a = op(a, unit())`
+/// 4. Existence of *inverse element*: `op(inverse(a), a) = unit() = op(a, inverse(a))`
+///
+/// # Examples
+/// [`crate::op::Sum`] over signed numbers and [`crate::op::Xor`] are [`Group`].
+/// ```
+/// use semigroup::{op::{Sum, Xor}, Group, Monoid, Semigroup};
+///
+/// assert_eq!(Sum(3).semigroup(Sum(3).inverse()), Sum::unit());
+/// assert_eq!(Xor(0b101).semigroup(Xor(0b101).inverse()), Xor::unit());
+/// ```
+///
+/// With *inverse element*, a range fold can be calculated from prefix folds,
+/// see [`crate::prefix_sum::PrefixSum`] and [`crate::fenwick_tree::FenwickTree`].
+///
+/// # Testing
+/// Use [`crate::assert_group!`] macro.
+///
+/// The *closure*, *associativity* and *identity element* properties are same as [`Monoid`],
+/// so they are guaranteed by [`crate::assert_monoid!`].
+/// However, existence of *inverse element* is not guaranteed the macro,
+/// so it must be verified manually using [`crate::assert_group!`].
+pub trait Group: Monoid {
+    fn inverse(self) -> Self;
+}
+
+#[cfg(any(test, feature = "test"))]
+pub mod test_group {
+    use std::fmt::Debug;
+
+    use crate::monoid::test_monoid::assert_monoid_impl;
+
+    use super::*;
+
+    /// Assert that the given type satisfies the *group* property.
+    ///
+    /// # Usage
+    /// Same to [`crate::assert_semigroup!`].
+    #[macro_export]
+    macro_rules! assert_group {
+        ($v:expr, seed = $seed:expr) => {
+            $crate::assert_group!(@pick $v, $crate::test_semigroup::Pick::Seed($seed))
+        };
+        ($v:expr, exhaustive) => {
+            $crate::assert_group!(@pick $v, $crate::test_semigroup::Pick::Exhaustive)
+        };
+        (@pick $v:expr, $pick:expr) => {
+            $crate::test_semigroup::for_each_pick3($v, $pick, |a, b, c| {
+                $crate::test_group::assert_group_impl(a.clone(), b.clone(), c.clone());
+            })
+        };
+        ($a:expr, $b: expr, $($tail: expr),*) => {
+            {
+                let v = vec![$a, $b, $($tail),*];
+                $crate::assert_group!(&v)
+            }
+        };
+        ($v:expr) => {
+            $crate::assert_group!(@pick $v, $crate::test_semigroup::Pick::Random)
+        };
+    }
+
+    pub fn assert_group_impl<T: Group + Clone + PartialEq + Debug>(a: T, b: T, c: T) {
+        assert_monoid_impl(a.clone(), b.clone(), c.clone());
+        assert_inverse_law(a.clone(), b.clone(), c.clone());
+    }
+
+    pub fn assert_inverse_law<T: Group + Clone + PartialEq + Debug>(a: T, b: T, c: T) {
+        assert_eq!(T::unit(), T::unit().inverse());
+        for x in [a.clone(), b.clone(), c.clone()] {
+            assert_eq!(T::unit(), T::op(x.clone().inverse(), x.clone()));
+            assert_eq!(T::unit(), T::op(x.clone(), x.clone().inverse()));
+            assert_eq!(x.clone(), x.clone().inverse().inverse());
+        }
+
+        let ab = T::op(a.clone(), b.clone());
+        assert_eq!(
+            ab.inverse(),
+            T::op(b.clone().inverse(), a.clone().inverse())
+        );
+        let bc = T::op(b.clone(), c.clone());
+        assert_eq!(
+            bc.inverse(),
+            T::op(c.clone().inverse(), b.clone().inverse())
+        );
+    }
+}
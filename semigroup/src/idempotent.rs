@@ -0,0 +1,130 @@
+use crate::{Reverse, Semigroup};
+
+/// [`Idempotent`] represents a binary operation that satisfies the following property
+/// 1. *Idempotency*: `op(a, a) = a`
+///
+/// The [*semigroup*](crate::Semigroup) set that satisfies the *idempotency* property is often called *band*.
+///
+/// # Construction
+/// [`Idempotent`] can be constructed by [`crate::Construction`], use `idempotent` attribute.
+/// ```
+/// use semigroup::{Construction, Semigroup};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, Construction)]
+/// #[construction(commutative, idempotent)]
+/// pub struct BitOr(u64);
+/// impl Semigroup for BitOr {
+///     fn op(base: Self, other: Self) -> Self {
+///         Self(base.0 | other.0)
+///     }
+/// }
+///
+/// let (a, b, c) = (BitOr(0b001), BitOr(0b010), BitOr(0b110));
+/// // #[test]
+/// semigroup::assert_idempotent!(&a, &b, &c);
+/// assert_eq!(a.semigroup(a), a);
+/// ```
+///
+/// # Deriving
+/// [`Idempotent`] can be derived like [`Semigroup`], use `idempotent` attribute.
+/// ```
+/// use semigroup::Semigroup;
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(idempotent, with = "semigroup::op::Coalesce")]
+/// pub struct ExampleStruct {
+///     pub name: Option<&'static str>,
+///     #[semigroup(with = "semigroup::op::Max")]
+///     pub max: u32,
+/// }
+///
+/// let a = ExampleStruct { name: None, max: 1 };
+/// let b = ExampleStruct { name: Some("b"), max: 10 };
+/// let c = ExampleStruct { name: Some("c"), max: 100 };
+///
+/// // #[test]
+/// semigroup::assert_idempotent!(&a, &b, &c);
+/// assert_eq!(b.clone().semigroup(b.clone()), b);
+/// ```
+///
+/// Like [`crate::Commutative`], the derive requires every field to be operated by an [`Idempotent`] operation.
+/// `skip` attribute keeps the base value, so it is supported, but `op` attribute is not.
+/// ```compile_fail
+/// use semigroup::Semigroup;
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(idempotent)]
+/// pub struct ExampleStruct {
+///     #[semigroup(with = "semigroup::op::Max")]
+///     pub max: u32,
+///     #[semigroup(with = "semigroup::op::Sum")]
+///     pub sum: u32,
+/// }
+/// ```
+///
+/// # Testing
+/// Use [`crate::assert_idempotent!`] macro.
+/// This is marker trait.
+///
+/// The *idempotency* property is not guaranteed by Rust’s type system,
+/// so it must be verified manually using [`crate::assert_idempotent!`].
+pub trait Idempotent: Semigroup {}
+
+impl<T: Idempotent> Idempotent for Reverse<T> {}
+
+#[cfg(any(test, feature = "test"))]
+pub mod test_idempotent {
+    use std::fmt::Debug;
+
+    use super::*;
+
+    /// Assert that the given type satisfies the *idempotency* property.
+    ///
+    /// # Usage
+    /// Same to [`crate::assert_semigroup!`].
+    #[macro_export]
+    macro_rules! assert_idempotent {
+        ($v:expr, seed = $seed:expr) => {
+            $crate::assert_idempotent!(@pick $v, $crate::test_semigroup::Pick::Seed($seed))
+        };
+        ($v:expr, exhaustive) => {
+            $crate::assert_idempotent!(@pick $v, $crate::test_semigroup::Pick::Exhaustive)
+        };
+        (@pick $v:expr, $pick:expr) => {
+            $crate::test_semigroup::for_each_pick3($v, $pick, |a, b, c| {
+                $crate::test_idempotent::assert_idempotent_impl(a.clone(), b.clone(), c.clone());
+            })
+        };
+        ($a:expr, $b: expr, $($tail: expr),*) => {
+            {
+                let v = vec![$a, $b, $($tail),*];
+                $crate::assert_idempotent!(&v)
+            }
+        };
+        ($v:expr) => {
+            $crate::assert_idempotent!(@pick $v, $crate::test_semigroup::Pick::Random)
+        };
+    }
+
+    pub fn assert_idempotent_impl<T: Idempotent + Clone + PartialEq + Debug>(a: T, b: T, c: T) {
+        assert_idempotent_law(a.clone(), b.clone(), c.clone());
+        assert_idempotent_reverse(a.clone(), b.clone(), c.clone());
+    }
+
+    pub fn assert_idempotent_law<T: Idempotent + Clone + PartialEq + Debug>(a: T, b: T, c: T) {
+        for x in [a.clone(), b.clone(), c.clone()] {
+            assert_eq!(x.clone(), T::op(x.clone(), x.clone()));
+        }
+        let ab = T::op(a.clone(), b.clone());
+        assert_eq!(ab.clone(), T::op(ab.clone(), ab.clone()));
+        let abc = T::op(ab.clone(), c.clone());
+        assert_eq!(abc.clone(), T::op(abc.clone(), abc.clone()));
+
+        let bc = T::op(b.clone(), c.clone());
+        assert_eq!(abc, T::op(ab.clone(), bc.clone()));
+    }
+
+    pub fn assert_idempotent_reverse<T: Idempotent + Clone + PartialEq + Debug>(a: T, b: T, c: T) {
+        for x in [Reverse(a.clone()), Reverse(b.clone()), Reverse(c.clone())] {
+            assert_eq!(x.clone().0, Reverse::<T>::op(x.clone(), x.clone()).0);
+        }
+    }
+}
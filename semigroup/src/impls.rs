@@ -0,0 +1,365 @@
+//! Blanket implementations for the standard library types.
+//!
+//! | type | operation | unit |
+//! | --- | --- | --- |
+//! | `()` | trivial | `()` |
+//! | `(A, B, ..)` up to 12 | pointwise | pointwise |
+//! | `[T; N]` | pointwise | pointwise |
+//! | `Option<T>` | operate if both are `Some` | `None` |
+//! | `Vec<T>`, `String` | concatenate | empty |
+//! | `Cow<str>`, `Cow<[T]>` | concatenate, borrow if one side is empty | empty |
+//! | `HashMap<K, V>`, `BTreeMap<K, V>` | union, operate values of the same key | empty |
+//! | `Box<T>`, `Rc<T>`, `Arc<T>` | operate inner values | inner unit |
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{Commutative, Idempotent, Semigroup};
+
+impl Semigroup for () {
+    fn op(_base: Self, _other: Self) -> Self {}
+}
+impl Commutative for () {}
+impl Idempotent for () {}
+
+macro_rules! impl_tuple {
+    ($($t:ident $i:tt),+) => {
+        impl<$($t: Semigroup),+> Semigroup for ($($t,)+) {
+            fn op(base: Self, other: Self) -> Self {
+                ($($t::op(base.$i, other.$i),)+)
+            }
+        }
+        impl<$($t: Commutative),+> Commutative for ($($t,)+) {}
+        impl<$($t: Idempotent),+> Idempotent for ($($t,)+) {}
+        #[cfg(feature = "monoid")]
+        impl<$($t: crate::Monoid),+> crate::Monoid for ($($t,)+) {
+            fn unit() -> Self {
+                ($($t::unit(),)+)
+            }
+        }
+    };
+}
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+impl<T: Semigroup, const N: usize> Semigroup for [T; N] {
+    fn op(base: Self, other: Self) -> Self {
+        let mut other = other.into_iter();
+        base.map(|b| T::op(b, other.next().unwrap_or_else(|| unreachable!())))
+    }
+}
+impl<T: Commutative, const N: usize> Commutative for [T; N] {}
+impl<T: Idempotent, const N: usize> Idempotent for [T; N] {}
+
+impl<T: Semigroup> Semigroup for Option<T> {
+    fn op(base: Self, other: Self) -> Self {
+        match (base, other) {
+            (Some(b), Some(o)) => Some(T::op(b, o)),
+            (b, None) => b,
+            (None, o) => o,
+        }
+    }
+}
+impl<T: Commutative> Commutative for Option<T> {}
+impl<T: Idempotent> Idempotent for Option<T> {}
+
+impl<T> Semigroup for Vec<T> {
+    fn op(mut base: Self, other: Self) -> Self {
+        base.extend(other);
+        base
+    }
+}
+impl Semigroup for String {
+    fn op(mut base: Self, other: Self) -> Self {
+        base.push_str(&other);
+        base
+    }
+}
+
+impl Semigroup for Cow<'_, str> {
+    fn op(base: Self, other: Self) -> Self {
+        if base.is_empty() {
+            other
+        } else if other.is_empty() {
+            base
+        } else {
+            base + other
+        }
+    }
+}
+impl<T: Clone> Semigroup for Cow<'_, [T]> {
+    fn op(mut base: Self, other: Self) -> Self {
+        if base.is_empty() {
+            other
+        } else if other.is_empty() {
+            base
+        } else {
+            base.to_mut().extend_from_slice(&other);
+            base
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Semigroup, S: BuildHasher> Semigroup for HashMap<K, V, S> {
+    fn op(mut base: Self, other: Self) -> Self {
+        for (k, v) in other {
+            let v = match base.remove(&k) {
+                Some(b) => V::op(b, v),
+                None => v,
+            };
+            base.insert(k, v);
+        }
+        base
+    }
+}
+impl<K: Eq + Hash, V: Commutative, S: BuildHasher> Commutative for HashMap<K, V, S> {}
+impl<K: Eq + Hash, V: Idempotent, S: BuildHasher> Idempotent for HashMap<K, V, S> {}
+
+impl<K: Ord, V: Semigroup> Semigroup for BTreeMap<K, V> {
+    fn op(mut base: Self, other: Self) -> Self {
+        for (k, v) in other {
+            let v = match base.remove(&k) {
+                Some(b) => V::op(b, v),
+                None => v,
+            };
+            base.insert(k, v);
+        }
+        base
+    }
+}
+impl<K: Ord, V: Commutative> Commutative for BTreeMap<K, V> {}
+impl<K: Ord, V: Idempotent> Idempotent for BTreeMap<K, V> {}
+
+impl<T: Semigroup> Semigroup for Box<T> {
+    fn op(base: Self, other: Self) -> Self {
+        Box::new(T::op(*base, *other))
+    }
+}
+impl<T: Commutative> Commutative for Box<T> {}
+impl<T: Idempotent> Idempotent for Box<T> {}
+
+impl<T: Semigroup + Clone> Semigroup for Rc<T> {
+    fn op(base: Self, other: Self) -> Self {
+        Rc::new(T::op(Rc::unwrap_or_clone(base), Rc::unwrap_or_clone(other)))
+    }
+}
+impl<T: Commutative + Clone> Commutative for Rc<T> {}
+impl<T: Idempotent + Clone> Idempotent for Rc<T> {}
+
+impl<T: Semigroup + Clone> Semigroup for Arc<T> {
+    fn op(base: Self, other: Self) -> Self {
+        Arc::new(T::op(
+            Arc::unwrap_or_clone(base),
+            Arc::unwrap_or_clone(other),
+        ))
+    }
+}
+impl<T: Commutative + Clone> Commutative for Arc<T> {}
+impl<T: Idempotent + Clone> Idempotent for Arc<T> {}
+
+#[cfg(feature = "monoid")]
+mod monoid {
+    use crate::Monoid;
+
+    use super::*;
+
+    impl Monoid for () {
+        fn unit() -> Self {}
+    }
+    impl<T: Monoid, const N: usize> Monoid for [T; N] {
+        fn unit() -> Self {
+            std::array::from_fn(|_| T::unit())
+        }
+    }
+    impl<T: Semigroup> Monoid for Option<T> {
+        fn unit() -> Self {
+            None
+        }
+    }
+    impl<T> Monoid for Vec<T> {
+        fn unit() -> Self {
+            Vec::new()
+        }
+    }
+    impl Monoid for String {
+        fn unit() -> Self {
+            String::new()
+        }
+    }
+    impl Monoid for Cow<'_, str> {
+        fn unit() -> Self {
+            Cow::Borrowed("")
+        }
+    }
+    impl<T: Clone> Monoid for Cow<'_, [T]> {
+        fn unit() -> Self {
+            Cow::Borrowed(&[])
+        }
+    }
+    impl<K: Eq + Hash, V: Semigroup, S: BuildHasher + Default> Monoid for HashMap<K, V, S> {
+        fn unit() -> Self {
+            HashMap::default()
+        }
+    }
+    impl<K: Ord, V: Semigroup> Monoid for BTreeMap<K, V> {
+        fn unit() -> Self {
+            BTreeMap::new()
+        }
+    }
+    impl<T: Monoid> Monoid for Box<T> {
+        fn unit() -> Self {
+            Box::new(T::unit())
+        }
+    }
+    impl<T: Monoid + Clone> Monoid for Rc<T> {
+        fn unit() -> Self {
+            Rc::new(T::unit())
+        }
+    }
+    impl<T: Monoid + Clone> Monoid for Arc<T> {
+        fn unit() -> Self {
+            Arc::new(T::unit())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_commutative, assert_idempotent, assert_monoid, assert_semigroup,
+        op::{Coalesce, Max, Min, Overwrite, Sum},
+        Monoid,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_unit_as_monoid() {
+        assert_monoid!((), (), ());
+        assert_commutative!((), (), ());
+        assert_idempotent!((), (), ());
+    }
+
+    #[test]
+    fn test_tuple_as_monoid() {
+        let a = (Sum(1), Max(2), Coalesce(None::<u8>));
+        let b = (Sum(3), Max(1), Coalesce(Some(1)));
+        let c = (Sum(5), Max(7), Coalesce(Some(2)));
+        assert_monoid!(&[a, b, c], exhaustive);
+        assert_eq!(a.semigroup(b), (Sum(4), Max(2), Coalesce(Some(1))));
+
+        let (x, y, z) = ((Sum(1), Min(2)), (Sum(3), Min(1)), (Sum(5), Min(7)));
+        assert_commutative!(x, y, z);
+        let (x, y, z) = ((Max(1), Min(2)), (Max(3), Min(1)), (Max(5), Min(7)));
+        assert_idempotent!(x, y, z);
+
+        assert_eq!(<(Sum<u8>,)>::unit(), (Sum(0),));
+        type S = Sum<u8>;
+        let twelve: (S, S, S, S, S, S, S, S, S, S, S, S) = [Sum(1); 12].into();
+        assert_eq!(twelve.semigroup(twelve), [Sum(2); 12].into());
+        assert_eq!(
+            <(S, S, S, S, S, S, S, S, S, S, S, S)>::unit(),
+            [Sum(0); 12].into()
+        );
+    }
+
+    #[test]
+    fn test_array_as_monoid() {
+        let a = [Sum(1), Sum(2), Sum(3)];
+        let b = [Sum(10), Sum(20), Sum(30)];
+        let c = [Sum(100), Sum(200), Sum(300)];
+        assert_monoid!(&[a, b, c], exhaustive);
+        assert_commutative!(a, b, c);
+        assert_eq!(a.semigroup(b), [Sum(11), Sum(22), Sum(33)]);
+        assert_eq!(<[Sum<i32>; 3]>::unit(), [Sum(0); 3]);
+        assert_idempotent!([Max(1), Max(2)], [Max(3), Max(0)], [Max(2), Max(2)]);
+    }
+
+    #[test]
+    fn test_option_as_monoid() {
+        let (a, b, c) = (Some(Sum(1)), None, Some(Sum(3)));
+        assert_monoid!(&[a, b, c], exhaustive);
+        assert_commutative!(a, b, c);
+        assert_eq!(a.semigroup(c), Some(Sum(4)));
+        assert_eq!(a.semigroup(b), Some(Sum(1)));
+        assert_idempotent!(Some(Max(1)), None, Some(Max(2)));
+    }
+
+    #[test]
+    fn test_vec_string_as_monoid() {
+        assert_monoid!(vec![1], vec![], vec![2, 3]);
+        assert_eq!(vec![1].semigroup(vec![2, 3]), vec![1, 2, 3]);
+        let (a, b, c) = ("a".to_string(), String::new(), "bc".to_string());
+        assert_monoid!(&[a.clone(), b, c.clone()], exhaustive);
+        assert_eq!(a.semigroup(c), "abc");
+    }
+
+    #[test]
+    fn test_cow_as_monoid() {
+        let (a, b, c) = (Cow::Borrowed("a"), Cow::Borrowed(""), Cow::Borrowed("bc"));
+        assert_monoid!(&[a.clone(), b.clone(), c.clone()], exhaustive);
+        assert!(matches!(a.clone().semigroup(b), Cow::Borrowed("a")));
+        assert!(matches!(a.semigroup(c), Cow::Owned(s) if s == "abc"));
+
+        let (a, b, c) = (
+            Cow::Borrowed(&[1][..]),
+            Cow::Borrowed(&[][..]),
+            Cow::Borrowed(&[2, 3][..]),
+        );
+        assert_monoid!(&[a.clone(), b.clone(), c.clone()], exhaustive);
+        assert!(matches!(b.semigroup(c.clone()), Cow::Borrowed(&[2, 3])));
+        assert_eq!(a.semigroup(c), Cow::<[i32]>::Owned(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_map_as_monoid() {
+        let a = HashMap::from([("x", Sum(1)), ("y", Sum(2))]);
+        let b = HashMap::from([("y", Sum(10)), ("z", Sum(20))]);
+        let c = HashMap::from([("x", Sum(100))]);
+        assert_monoid!(&[a.clone(), b.clone(), c.clone()], exhaustive);
+        assert_commutative!(a.clone(), b.clone(), c.clone());
+        assert_eq!(
+            a.semigroup(b),
+            HashMap::from([("x", Sum(1)), ("y", Sum(12)), ("z", Sum(20))])
+        );
+
+        let a = BTreeMap::from([(1, Overwrite("a")), (2, Overwrite("b"))]);
+        let b = BTreeMap::from([(2, Overwrite("c"))]);
+        let c = BTreeMap::from([(1, Overwrite("d")), (3, Overwrite("e"))]);
+        assert_monoid!(&[a.clone(), b.clone(), c.clone()], exhaustive);
+        assert_idempotent!(a.clone(), b.clone(), c.clone());
+        assert_eq!(
+            a.semigroup(b),
+            BTreeMap::from([(1, Overwrite("a")), (2, Overwrite("c"))])
+        );
+    }
+
+    #[test]
+    fn test_pointer_as_monoid() {
+        assert_monoid!(Box::new(Sum(1)), Box::new(Sum(2)), Box::new(Sum(3)));
+        assert_commutative!(Rc::new(Sum(1)), Rc::new(Sum(2)), Rc::new(Sum(3)));
+        assert_commutative!(Arc::new(Sum(1)), Arc::new(Sum(2)), Arc::new(Sum(3)));
+        assert_semigroup!(
+            Arc::new(Overwrite(1)),
+            Arc::new(Overwrite(2)),
+            Arc::new(Overwrite(3))
+        );
+        let shared = Arc::new(Sum(1));
+        assert_eq!(shared.clone().semigroup(Arc::new(Sum(2))), Arc::new(Sum(3)));
+        assert_eq!(shared, Arc::new(Sum(1)));
+    }
+}
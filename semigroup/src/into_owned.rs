@@ -0,0 +1,105 @@
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+/// [`IntoOwned`] converts a value that may borrow data into a value that owns it.
+///
+/// Values can be merged from borrowed source buffers by [`crate::Semigroup`] without cloning,
+/// and then be materialized only once at the end.
+///
+/// # Deriving
+/// [`IntoOwned`] can be derived with [`crate::Semigroup`] for structs, use `into_owned` attribute.
+/// Lifetime parameters of the struct are replaced by `'static`, and fields that mention them are converted by [`IntoOwned`].
+/// So such fields should be [`Cow`] rather than references.
+/// ```
+/// use std::borrow::Cow;
+/// use semigroup::{IntoOwned, Semigroup};
+///
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(into_owned, with = "semigroup::op::Coalesce")]
+/// pub struct Config<'a> {
+///     pub host: Option<Cow<'a, str>>,
+///     pub user: Option<Cow<'a, str>>,
+///     #[semigroup(with = "semigroup::op::Concat")]
+///     pub tags: Vec<Cow<'a, str>>,
+/// }
+///
+/// fn load(file: &str, env: &str) -> Config<'static> {
+///     let a = Config { host: Some(file.into()), user: None, tags: vec![file.into()] };
+///     let b = Config { host: None, user: Some(env.into()), tags: vec![env.into()] };
+///     a.semigroup(b).into_owned()
+/// }
+///
+/// let config = load(&String::from("localhost"), &String::from("alice"));
+/// assert_eq!(config.host.as_deref(), Some("localhost"));
+/// assert_eq!(config.user.as_deref(), Some("alice"));
+/// assert_eq!(config.tags, vec!["localhost", "alice"]);
+/// ```
+///
+/// Note that [`Cow::into_owned`] is an inherent method that returns the owned data,
+/// so call `IntoOwned::into_owned(cow)` to keep [`Cow`].
+pub trait IntoOwned {
+    type Owned;
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<B: ?Sized + ToOwned + 'static> IntoOwned for Cow<'_, B> {
+    type Owned = Cow<'static, B>;
+    fn into_owned(self) -> Cow<'static, B> {
+        Cow::Owned(Cow::into_owned(self))
+    }
+}
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(T::into_owned).collect()
+    }
+}
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned = Box<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Box::new(T::into_owned(*self))
+    }
+}
+impl<T: IntoOwned + Clone> IntoOwned for Rc<T> {
+    type Owned = Rc<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Rc::new(T::into_owned(Rc::unwrap_or_clone(self)))
+    }
+}
+impl<T: IntoOwned + Clone> IntoOwned for Arc<T> {
+    type Owned = Arc<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Arc::new(T::into_owned(Arc::unwrap_or_clone(self)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cow_into_owned() {
+        let buffer = String::from("borrowed");
+        let owned: Cow<'static, str> = IntoOwned::into_owned(Cow::Borrowed(buffer.as_str()));
+        drop(buffer);
+        assert!(matches!(owned, Cow::Owned(ref s) if s == "borrowed"));
+    }
+
+    #[test]
+    fn test_container_into_owned() {
+        let buffer = String::from("borrowed");
+        let option = Some(Cow::Borrowed(buffer.as_str()));
+        let vec = vec![Cow::Borrowed(buffer.as_str()), Cow::Borrowed("static")];
+        let arc = Arc::new(Cow::Borrowed(buffer.as_str()));
+        let (option, vec, arc) = (option.into_owned(), vec.into_owned(), arc.into_owned());
+        drop(buffer);
+        assert_eq!(option.as_deref(), Some("borrowed"));
+        assert_eq!(vec, vec!["borrowed", "static"]);
+        assert_eq!(arc.as_ref(), "borrowed");
+    }
+}
@@ -0,0 +1,514 @@
+use std::fmt::{self, Display};
+
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+
+use crate::{Idempotent, Semigroup};
+
+/// [`Layers`] merges multiple [`Deserializer`]s such as config file, environment variables and command line arguments
+/// by the [`Semigroup`] of the deserialized type.
+///
+/// Each layer is deserialized into a self-describing tree at first, so that a layer can be partial.
+/// When merging, missing fields of a layer are filled with the fields of its nearest layer that has them,
+/// and then each layer is deserialized into the type and operated in order of [`Layers::layer`].
+/// A filled field repeats the value of a neighboring layer, so the type must be [`Idempotent`],
+/// such as derived with `idempotent` attribute and fields of [`crate::op::Coalesce`] or [`crate::op::Overwrite`].
+/// Then the repeated values are operated as if they were given only once.
+///
+/// String values are parsed into numbers or booleans if required, such as environment variables.
+/// Errors of a value point at the layer that the value comes from.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use semigroup::{layer::Layers, Semigroup};
+/// use serde::de::value::MapDeserializer;
+///
+/// #[derive(Debug, Clone, PartialEq, serde::Deserialize, Semigroup)]
+/// #[semigroup(idempotent, with = "semigroup::op::Overwrite")]
+/// struct Config {
+///     host: String,
+///     port: u16,
+///     #[semigroup(with = "semigroup::op::Coalesce")]
+///     user: Option<String>,
+/// }
+///
+/// let file = r#"{ "host": "localhost", "port": 8080 }"#;
+/// let env = HashMap::from([("port", "3000"), ("user", "alice")]);
+///
+/// let config: Config = Layers::new()
+///     .layer("file", &mut serde_json::Deserializer::from_str(file))?
+///     .layer("env", MapDeserializer::<_, serde::de::value::Error>::new(env.into_iter()))?
+///     .merge()?;
+/// assert_eq!(config, Config { host: "localhost".to_string(), port: 3000, user: Some("alice".to_string()) });
+///
+/// let env = HashMap::from([("port", "http")]);
+/// let err = Layers::new()
+///     .layer("file", &mut serde_json::Deserializer::from_str(file))?
+///     .layer("env", MapDeserializer::<_, serde::de::value::Error>::new(env.into_iter()))?
+///     .merge::<Config>()
+///     .unwrap_err();
+/// assert_eq!(err.layer(), Some("env"));
+/// # Ok::<(), semigroup::layer::LayerError>(())
+/// ```
+///
+/// Non-idempotent fields such as [`crate::op::Sum`] cannot be merged, because the filled fields would be counted twice.
+/// ```compile_fail
+/// use semigroup::{layer::Layers, Semigroup};
+///
+/// #[derive(Debug, Clone, PartialEq, serde::Deserialize, Semigroup)]
+/// #[semigroup(idempotent)]
+/// struct Config {
+///     #[semigroup(with = "semigroup::op::Sum")]
+///     retries: u32,
+///     #[semigroup(with = "semigroup::op::Overwrite")]
+///     name: String,
+/// }
+///
+/// let config: Config = Layers::new()
+///     .layer("file", &mut serde_json::Deserializer::from_str(r#"{ "retries": 3, "name": "a" }"#))?
+///     .layer("cli", &mut serde_json::Deserializer::from_str(r#"{ "name": "b" }"#))?
+///     .merge()?;
+/// # Ok::<(), semigroup::layer::LayerError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Layers {
+    names: Vec<String>,
+    roots: Vec<Node>,
+}
+impl Layers {
+    /// Init empty layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Deserialize `deserializer` as the next layer named `name`.
+    pub fn layer<'de, D>(
+        mut self,
+        name: impl Into<String>,
+        deserializer: D,
+    ) -> Result<Self, LayerError>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = name.into();
+        let layer = self.names.len();
+        let root = NodeSeed { layer }
+            .deserialize(deserializer)
+            .map_err(|e| LayerError::new(Some(name.clone()), e))?;
+        self.names.push(name);
+        self.roots.push(root);
+        Ok(self)
+    }
+    /// Return the number of layers.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+    /// Check if there is no layer.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Deserialize each layer into `T` and merge them by [`Semigroup::op`].
+    pub fn merge<T>(&self) -> Result<T, LayerError>
+    where
+        T: DeserializeOwned + Idempotent,
+    {
+        let roots: Vec<_> = self.roots.iter().map(Some).collect();
+        (0..roots.len())
+            .map(|i| {
+                let node = fill(&roots, i).unwrap_or_else(|| unreachable!("layer {i} must exist"));
+                T::deserialize(NodeDeserializer::new(&node, &self.names))
+            })
+            .reduce(|base, other| Ok(Semigroup::op(base?, other?)))
+            .unwrap_or_else(|| Err(de::Error::custom("no layers to merge")))
+    }
+}
+
+/// Error of [`Layers`], that may point at the layer which caused it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerError {
+    layer: Option<String>,
+    message: String,
+}
+impl LayerError {
+    fn new<E: Display>(layer: Option<String>, err: E) -> Self {
+        let message = err.to_string();
+        Self { layer, message }
+    }
+    /// Return the name of the layer which caused this error.
+    pub fn layer(&self) -> Option<&str> {
+        self.layer.as_deref()
+    }
+    /// Return the error message without the layer name.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+impl Display for LayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.layer {
+            Some(layer) => write!(f, "layer `{layer}`: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+impl std::error::Error for LayerError {}
+impl de::Error for LayerError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(None, msg)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    layer: usize,
+    value: Value,
+}
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Char(char),
+    String(String),
+    Bytes(Vec<u8>),
+    Unit,
+    None,
+    Some(Box<Node>),
+    Seq(Vec<Node>),
+    Map(Vec<(Node, Node)>),
+}
+
+/// Complete the `i`-th node by the nearest nodes, recursively for maps.
+fn fill(nodes: &[Option<&Node>], i: usize) -> Option<Node> {
+    let Some(own) = nodes[i] else {
+        let nearest = (0..i)
+            .rev()
+            .chain(i + 1..nodes.len())
+            .find(|&j| nodes[j].is_some())?;
+        return fill(nodes, nearest);
+    };
+    let Value::Map(own_entries) = &own.value else {
+        return Some(own.clone());
+    };
+    let maps: Vec<_> = nodes
+        .iter()
+        .map(|node| match node {
+            Some(Node {
+                value: Value::Map(entries),
+                ..
+            }) => Some(entries),
+            _ => None,
+        })
+        .collect();
+    let mut keys: Vec<&Node> = Vec::new();
+    for (key, _) in own_entries
+        .iter()
+        .chain(maps.iter().flatten().flat_map(|e| e.iter()))
+    {
+        if !keys.iter().any(|k| k.value == key.value) {
+            keys.push(key);
+        }
+    }
+    let entries = keys
+        .into_iter()
+        .filter_map(|key| {
+            let children: Vec<_> = maps
+                .iter()
+                .map(|m| {
+                    m.and_then(|e| e.iter().find(|(k, _)| k.value == key.value))
+                        .map(|(_, v)| v)
+                })
+                .collect();
+            Some((key.clone(), fill(&children, i)?))
+        })
+        .collect();
+    Some(Node {
+        layer: own.layer,
+        value: Value::Map(entries),
+    })
+}
+
+struct NodeSeed {
+    layer: usize,
+}
+impl<'de> DeserializeSeed<'de> for NodeSeed {
+    type Value = Node;
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+impl NodeSeed {
+    fn node(&self, value: Value) -> Node {
+        Node {
+            layer: self.layer,
+            value,
+        }
+    }
+}
+impl<'de> Visitor<'de> for NodeSeed {
+    type Value = Node;
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any self-describing value")
+    }
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(self.node(Value::Bool(v)))
+    }
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(self.node(Value::I64(v)))
+    }
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(self.node(Value::U64(v)))
+    }
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(self.node(Value::F64(v)))
+    }
+    fn visit_char<E>(self, v: char) -> Result<Self::Value, E> {
+        Ok(self.node(Value::Char(v)))
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.node(Value::String(v.to_string())))
+    }
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(self.node(Value::String(v)))
+    }
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(self.node(Value::Bytes(v.to_vec())))
+    }
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(self.node(Value::Bytes(v)))
+    }
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(self.node(Value::Unit))
+    }
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(self.node(Value::None))
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let some = NodeSeed { layer: self.layer }.deserialize(deserializer)?;
+        Ok(self.node(Value::Some(Box::new(some))))
+    }
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut nodes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(node) = seq.next_element_seed(NodeSeed { layer: self.layer })? {
+            nodes.push(node);
+        }
+        Ok(self.node(Value::Seq(nodes)))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        let layer = self.layer;
+        while let Some(entry) = map.next_entry_seed(NodeSeed { layer }, NodeSeed { layer })? {
+            entries.push(entry);
+        }
+        Ok(self.node(Value::Map(entries)))
+    }
+}
+
+struct NodeDeserializer<'a> {
+    node: &'a Node,
+    names: &'a [String],
+}
+impl<'a> NodeDeserializer<'a> {
+    fn new(node: &'a Node, names: &'a [String]) -> Self {
+        Self { node, names }
+    }
+    fn child(&self, node: &'a Node) -> Self {
+        Self::new(node, self.names)
+    }
+    /// point at the layer of this node, if the error does not point at any layer yet.
+    fn attribute(&self, mut err: LayerError) -> LayerError {
+        err.layer = err
+            .layer
+            .or_else(|| self.names.get(self.node.layer).cloned());
+        err
+    }
+}
+impl<'de> IntoDeserializer<'de, LayerError> for NodeDeserializer<'de> {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+macro_rules! deserialize_parse {
+    ($($deserialize:ident => $visit:ident($ty:ty)),* $(,)?) => {$(
+        fn $deserialize<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match &self.node.value {
+                Value::String(s) => match s.parse::<$ty>() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Str(s), &visitor)),
+                }
+                .map_err(|e| self.attribute(e)),
+                _ => self.deserialize_any(visitor),
+            }
+        }
+    )*};
+}
+impl<'de> Deserializer<'de> for NodeDeserializer<'de> {
+    type Error = LayerError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match &self.node.value {
+            Value::Bool(v) => visitor.visit_bool(*v),
+            Value::I64(v) => visitor.visit_i64(*v),
+            Value::U64(v) => visitor.visit_u64(*v),
+            Value::F64(v) => visitor.visit_f64(*v),
+            Value::Char(v) => visitor.visit_char(*v),
+            Value::String(v) => visitor.visit_str(v),
+            Value::Bytes(v) => visitor.visit_bytes(v),
+            Value::Unit => visitor.visit_unit(),
+            Value::None => visitor.visit_none(),
+            Value::Some(node) => return visitor.visit_some(self.child(node)),
+            Value::Seq(nodes) => {
+                let mut seq = SeqDeserializer::new(nodes.iter().map(|n| self.child(n)));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                return Ok(value);
+            }
+            Value::Map(entries) => {
+                let entries = entries.iter().map(|(k, v)| (self.child(k), self.child(v)));
+                let mut map = MapDeserializer::new(entries);
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                return Ok(value);
+            }
+        }
+        .map_err(|e| self.attribute(e))
+    }
+
+    deserialize_parse! {
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match &self.node.value {
+            Value::None | Value::Unit => visitor.visit_none(),
+            Value::Some(node) => visitor.visit_some(self.child(node)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match &self.node.value {
+            Value::String(variant) => visitor
+                .visit_enum(variant.as_str().into_deserializer())
+                .map_err(|e| self.attribute(e)),
+            Value::Map(entries) if entries.len() == 1 => {
+                let (variant, value) = &entries[0];
+                let (variant, value) = (self.child(variant), self.child(value));
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct EnumDeserializer<'a> {
+    variant: NodeDeserializer<'a>,
+    value: NodeDeserializer<'a>,
+}
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = LayerError;
+    type Variant = NodeDeserializer<'de>;
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        Ok((seed.deserialize(self.variant)?, self.value))
+    }
+}
+impl<'de> VariantAccess<'de> for NodeDeserializer<'de> {
+    type Error = LayerError;
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self)
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[test]
+    fn test_fill_nearest() {
+        let json = |s| serde_json::Deserializer::from_str(s);
+        let layers = Layers::new()
+            .layer("a", &mut json(r#"{ "x": 1 }"#))
+            .unwrap()
+            .layer("b", &mut json(r#"{ "x": 2, "y": 2 }"#))
+            .unwrap()
+            .layer("c", &mut json(r#"{}"#))
+            .unwrap();
+        let roots: Vec<_> = layers.roots.iter().map(Some).collect();
+        let filled: Vec<_> = (0..3)
+            .map(|i| {
+                let node = fill(&roots, i).unwrap();
+                HashMap::<String, u32>::deserialize(NodeDeserializer::new(&node, &layers.names))
+                    .unwrap()
+            })
+            .collect();
+        let expected = |x, y| HashMap::from([("x".to_string(), x), ("y".to_string(), y)]);
+        assert_eq!(filled, vec![expected(1, 2), expected(2, 2), expected(2, 2)]);
+    }
+}
@@ -85,6 +85,25 @@
 //! # }
 //! ```
 //!
+//! ### Aggregate with sketch
+//! Only available with the `sketch` feature. More detail is in [`op::TDigest`] and [`op::DDSketch`].
+//! ```
+//! # #[cfg(feature="sketch")]
+//! # {
+//! use semigroup::{op::{DDSketch, TDigest}, Semigroup};
+//!
+//! let digest1 = (-500..0).map(f64::from).collect::<TDigest>();
+//! let digest2 = (0..500).map(f64::from).collect::<TDigest>();
+//! let digest = digest1.semigroup(digest2);
+//! assert_eq!(digest.min(), Some(-500.0));
+//!
+//! let sketch1 = [0.25, 0.5].into_iter().collect::<DDSketch>();
+//! let sketch2 = [-1.5, 3.0].into_iter().collect::<DDSketch>();
+//! let sketch = sketch1.semigroup(sketch2);
+//! assert!((sketch.quantile(0.0).unwrap() + 1.5).abs() <= 1.5 * 0.01);
+//! # }
+//! ```
+//!
 //! ### Aggregate request-response result
 //! Only available with the `histogram` feature.
 //! ```
@@ -147,6 +166,29 @@
 //! # }
 //! ```
 //!
+//! ### Aggregate in parallel
+//! Only available with the `rayon` feature. More detail is in [`ParallelSemigroupIterator`] and [`ParallelCommutativeIterator`].
+//! ```
+//! # #[cfg(feature="rayon")]
+//! # {
+//! use rayon::prelude::*;
+//! use semigroup::{op::{Max, Sum}, ParallelCommutativeIterator, Semigroup};
+//!
+//! #[derive(Debug, Clone, PartialEq, Semigroup)]
+//! #[semigroup(commutative)]
+//! pub struct Aggregate {
+//!     count: Sum<u64>,
+//!     max: Max<u64>,
+//! }
+//!
+//! let aggregate = (0..100000)
+//!     .into_par_iter()
+//!     .map(|i| Aggregate { count: Sum(1), max: Max(i) })
+//!     .par_commutative_reduce();
+//! assert_eq!(aggregate, Some(Aggregate { count: Sum(100000), max: Max(99999) }));
+//! # }
+//! ```
+//!
 //! ## Segment tree
 //! More detail is in [`segment_tree::SegmentTree`] that requires [`Monoid`].
 //! ### Range sum
@@ -193,6 +235,22 @@
 //! # }
 //! ```
 //!
+//! ### Range update
+//! Only available with the `monoid` feature. More detail is in [`segment_tree::lazy::LazySegmentTree`] that requires [`MonoidAction`].
+//! ```
+//! # #[cfg(feature="monoid")]
+//! # {
+//! use semigroup::{op::Sum, Construction, segment_tree::lazy::LazySegmentTree};
+//! let data = 0..=10000;
+//! let mut sum_tree: LazySegmentTree<_, Sum<_>> = data.into_iter().map(Sum).collect();
+//! assert_eq!(sum_tree.fold(3..6).into_inner(), 12);
+//! sum_tree.apply(4..=5, Sum(50));
+//! sum_tree.apply(.., Sum(1));
+//! assert_eq!(sum_tree.fold(3..6).into_inner(), 115);
+//! assert_eq!(sum_tree.fold(..).into_inner(), 50015101);
+//! # }
+//! ```
+//!
 //! # Links
 //! - GitHub: <https://github.com/hayas1/semigroup>
 //! - GitHub Pages: <https://hayas1.github.io/semigroup/semigroup>
@@ -207,21 +265,55 @@
 //! <https://hayas1.github.io/semigroup/semigroup/tarpaulin-report.html>
 //!
 
+#[cfg(feature = "monoid")]
+mod action;
 mod annotate;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+mod arbitrary;
 mod commutative;
 mod construction;
+#[cfg(feature = "monoid")]
+pub mod fenwick_tree;
+#[cfg(feature = "monoid")]
+mod group;
+mod idempotent;
+mod impls;
+mod into_owned;
 mod iter;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod layer;
 #[cfg(feature = "monoid")]
 mod monoid;
 pub mod op;
+#[cfg(feature = "rayon")]
+mod par_iter;
+#[cfg(feature = "monoid")]
+pub mod prefix_sum;
+mod provenance;
+mod range;
 #[cfg(feature = "monoid")]
 pub mod segment_tree;
 mod semigroup;
+pub mod sliding_window;
+pub mod sparse_table;
+mod try_semigroup;
 
-pub use self::{annotate::*, commutative::*, construction::*, iter::*, semigroup::*};
+pub use self::{
+    annotate::*, commutative::*, construction::*, idempotent::*, into_owned::*, iter::*,
+    provenance::*, semigroup::*, try_semigroup::*,
+};
 
 #[cfg(feature = "monoid")]
-pub use self::monoid::*;
+pub use self::{action::*, group::*, monoid::*};
+
+#[cfg(feature = "rayon")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "rayon")))]
+pub use self::par_iter::*;
+
+#[cfg(all(any(test, feature = "test"), feature = "proptest"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "test", feature = "proptest"))))]
+pub use self::arbitrary::test_proptest;
 
 #[cfg(feature = "derive")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "derive")))]
@@ -1,6 +1,6 @@
 use semigroup_derive::ConstructionPriv;
 
-use crate::{Annotate, Annotated, AnnotatedSemigroup, Semigroup};
+use crate::{Annotate, Annotated, AnnotatedSemigroup, Idempotent, Semigroup};
 
 /// [`Monoid`] represents a binary operation that satisfies the following properties
 /// 1. *Closure*: `op: T × T → T`
@@ -28,6 +28,23 @@ use crate::{Annotate, Annotated, AnnotatedSemigroup, Semigroup};
 /// assert_eq!(a.semigroup(b).semigroup(c), ExampleStruct { str: Some("ten"), sum: 110 });
 /// ```
 ///
+/// For enums, a unit variant with `unit` attribute is the *identity element*.
+/// ```
+/// use semigroup::{Semigroup, Monoid};
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(monoid)]
+/// pub enum ExampleEnum {
+///     #[semigroup(unit)]
+///     Empty,
+///     Sum(#[semigroup(with = "semigroup::op::Sum")] u32),
+/// }
+///
+/// let (a, b, c) = (ExampleEnum::unit(), ExampleEnum::Sum(10), ExampleEnum::Sum(100));
+/// // #[test]
+/// semigroup::assert_monoid!(&a, &b, &c);
+/// assert_eq!(a.semigroup(b).semigroup(c), ExampleEnum::Sum(110));
+/// ```
+///
 /// # Construction
 /// [`Monoid`] can be constructed by [`crate::ConstructionMonoid`] like [`Semigroup`], use `monoid` attribute.
 ///
@@ -121,6 +138,7 @@ pub trait AnnotatedMonoid<A>: Sized + Monoid + AnnotatedSemigroup<A> {
 /// assert_eq!(bd.as_ref().unwrap().duration(), Duration::from_millis(250));
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[construction(monoid, unit = Self(None))]
 pub struct OptionMonoid<T: Semigroup>(pub Option<T>);
 impl<T: Semigroup> From<T> for OptionMonoid<T> {
@@ -142,6 +160,7 @@ impl<T: Semigroup> Semigroup for OptionMonoid<T> {
         }
     }
 }
+impl<T: Idempotent> Idempotent for OptionMonoid<T> {}
 impl<T: AnnotatedSemigroup<A>, A> AnnotatedSemigroup<Option<A>> for OptionMonoid<T> {
     fn annotated_op(
         base: Annotated<Self, Option<A>>,
@@ -159,6 +178,11 @@ impl<T: AnnotatedSemigroup<A>, A> AnnotatedSemigroup<Option<A>> for OptionMonoid
             _ => unreachable!(), // TODO safety annotation
         }
     }
+    fn offers_value(&self) -> bool {
+        self.0
+            .as_ref()
+            .is_some_and(<T as AnnotatedSemigroup<A>>::offers_value)
+    }
 }
 impl<T: AnnotatedSemigroup<A> + Annotate<A>, A> Annotate<Option<A>> for OptionMonoid<T> {
     type Annotation = T::Annotation;
@@ -184,6 +208,17 @@ pub mod test_monoid {
     /// Same to [`crate::assert_semigroup!`].
     #[macro_export]
     macro_rules! assert_monoid {
+        ($v:expr, seed = $seed:expr) => {
+            $crate::assert_monoid!(@pick $v, $crate::test_semigroup::Pick::Seed($seed))
+        };
+        ($v:expr, exhaustive) => {
+            $crate::assert_monoid!(@pick $v, $crate::test_semigroup::Pick::Exhaustive)
+        };
+        (@pick $v:expr, $pick:expr) => {
+            $crate::test_semigroup::for_each_pick3($v, $pick, |a, b, c| {
+                $crate::test_monoid::assert_monoid_impl(a.clone(), b.clone(), c.clone());
+            })
+        };
         ($a:expr, $b: expr, $($tail: expr),*) => {
             {
                 let v = vec![$a, $b, $($tail),*];
@@ -191,10 +226,7 @@ pub mod test_monoid {
             }
         };
         ($v:expr) => {
-            {
-                let (a, b, c) = $crate::test_semigroup::pick3($v);
-                $crate::test_monoid::assert_monoid_impl(a.clone(), b.clone(), c.clone());
-            }
+            $crate::assert_monoid!(@pick $v, $crate::test_semigroup::Pick::Random)
         };
     }
 
@@ -229,3 +261,29 @@ pub mod test_monoid {
         assert_associative_law(a.clone(), b.clone(), T::unit());
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::op::Coalesce;
+
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let some = OptionMonoid::from(Coalesce(Some(1)));
+        let json = serde_json::to_string(&some).unwrap();
+        assert_eq!(json, "1");
+        assert_eq!(
+            serde_json::from_str::<OptionMonoid<Coalesce<i32>>>(&json).unwrap(),
+            some
+        );
+
+        let none = OptionMonoid::<Coalesce<i32>>::unit();
+        let json = serde_json::to_string(&none).unwrap();
+        assert_eq!(json, "null");
+        assert_eq!(
+            serde_json::from_str::<OptionMonoid<Coalesce<i32>>>(&json).unwrap(),
+            none
+        );
+    }
+}
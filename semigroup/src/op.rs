@@ -1,7 +1,15 @@
 mod coalesce;
+mod coalesce_by;
+mod coalesce_last;
+mod coalesce_non_empty;
 mod concat;
+mod merge_map;
 mod overwrite;
-pub use {coalesce::*, concat::*, overwrite::*};
+mod unique;
+pub use {
+    coalesce::*, coalesce_by::*, coalesce_last::*, coalesce_non_empty::*, concat::*, merge_map::*,
+    overwrite::*, unique::*,
+};
 
 #[cfg(feature = "monoid")]
 mod gcd;
@@ -9,14 +17,23 @@ mod gcd;
 mod lcm;
 mod max;
 mod min;
+#[cfg(feature = "monoid")]
+mod moments;
 mod prod;
 mod sum;
 mod xor;
 #[cfg(feature = "monoid")]
-pub use {gcd::*, lcm::*};
+pub use {gcd::*, lcm::*, moments::*};
 pub use {max::*, min::*, prod::*, sum::*, xor::*};
 
 #[cfg(feature = "histogram")]
 mod hdr_histogram;
 #[cfg(feature = "histogram")]
 pub use hdr_histogram::*;
+
+#[cfg(feature = "sketch")]
+mod dd_sketch;
+#[cfg(feature = "sketch")]
+mod t_digest;
+#[cfg(feature = "sketch")]
+pub use {dd_sketch::*, t_digest::*};
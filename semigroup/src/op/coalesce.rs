@@ -1,6 +1,6 @@
 use semigroup_derive::{properties_priv, ConstructionPriv};
 
-use crate::{Annotated, AnnotatedSemigroup};
+use crate::{Annotated, AnnotatedSemigroup, IntoOwned};
 
 /// A semigroup construction that returns the first non-`None` value.
 /// # Properties
@@ -17,8 +17,8 @@ use crate::{Annotated, AnnotatedSemigroup};
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[construction(annotated, monoid, unit = Self(None))]
-#[properties_priv(annotated, monoid)]
+#[construction(annotated, monoid, idempotent, unit = Self(None))]
+#[properties_priv(selective)]
 pub struct Coalesce<T>(pub Option<T>);
 impl<T, A> AnnotatedSemigroup<A> for Coalesce<T> {
     fn annotated_op(base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A> {
@@ -27,11 +27,20 @@ impl<T, A> AnnotatedSemigroup<A> for Coalesce<T> {
             (None, Some(_)) => other,
         }
     }
+    fn offers_value(&self) -> bool {
+        self.0.is_some()
+    }
+}
+impl<T: IntoOwned> IntoOwned for Coalesce<T> {
+    type Owned = Coalesce<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Coalesce(self.0.map(T::into_owned))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_monoid, assert_semigroup, Construction, Semigroup};
+    use crate::{assert_idempotent, assert_monoid, assert_semigroup, Construction, Semigroup};
 
     use super::*;
 
@@ -59,6 +68,12 @@ mod tests {
         assert_monoid!(a, b, c);
     }
 
+    #[test]
+    fn test_coalesce_idempotent() {
+        let (a, b, c) = (Coalesce(Some(1)), Coalesce(Some(2)), Coalesce(Some(3)));
+        assert_idempotent!(a, b, c);
+    }
+
     #[test]
     fn test_coalesce() {
         let (a, b) = (Coalesce(None), Coalesce(Some("value")));
@@ -0,0 +1,229 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use semigroup_derive::properties_priv;
+
+use crate::{
+    Annotate, Annotated, AnnotatedSemigroup, Construction, ConstructionAnnotated, Idempotent,
+    Semigroup,
+};
+
+/// A predicate that decides whether a value is present for [`CoalesceBy`].
+pub trait CoalescePredicate<T> {
+    fn is_present(value: &T) -> bool;
+}
+
+/// A semigroup construction that returns the first value satisfying the predicate `P`.
+/// If no value satisfies `P`, returns the first non-`None` value.
+///
+/// To use it as `with` of [`crate::Semigroup`] derive, give the predicate by type alias.
+/// The predicate is only a marker, so the standard traits such as [`Clone`] and [`PartialEq`] do not require it.
+/// # Properties
+/// <!-- properties -->
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::{CoalesceBy, CoalescePredicate}, Construction, Semigroup};
+///
+/// struct NonZero;
+/// impl CoalescePredicate<u16> for NonZero {
+///     fn is_present(value: &u16) -> bool {
+///         *value != 0
+///     }
+/// }
+/// type CoalesceNonZero<T> = CoalesceBy<T, NonZero>;
+///
+/// let a = CoalesceNonZero::new(Some(0));
+/// let b = CoalesceNonZero::new(Some(8080));
+/// assert_eq!(a.semigroup(b).into_inner(), Some(8080));
+///
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(with = "CoalesceNonZero")]
+/// struct Server {
+///     port: Option<u16>,
+/// }
+/// let server = Server { port: Some(0) }.semigroup(Server { port: None });
+/// assert_eq!(server.port, Some(0));
+/// ```
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[properties_priv(annotated, monoid, idempotent, selective, unit = Self::new(None))]
+pub struct CoalesceBy<T, P>(
+    pub Option<T>,
+    #[cfg_attr(feature = "serde", serde(skip))] PhantomData<P>,
+);
+impl<T, P> CoalesceBy<T, P> {
+    pub fn new(value: Option<T>) -> Self {
+        Self(value, PhantomData)
+    }
+}
+impl<T: Debug, P> Debug for CoalesceBy<T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CoalesceBy").field(&self.0).finish()
+    }
+}
+impl<T: Clone, P> Clone for CoalesceBy<T, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+impl<T: Copy, P> Copy for CoalesceBy<T, P> {}
+impl<T: PartialEq, P> PartialEq for CoalesceBy<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<T: Eq, P> Eq for CoalesceBy<T, P> {}
+impl<T: PartialOrd, P> PartialOrd for CoalesceBy<T, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+impl<T: Ord, P> Ord for CoalesceBy<T, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+impl<T, P> Default for CoalesceBy<T, P> {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+impl<T: Hash, P> Hash for CoalesceBy<T, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+impl<T, P: CoalescePredicate<T>, A> AnnotatedSemigroup<A> for CoalesceBy<T, P> {
+    fn annotated_op(base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A> {
+        let (b, o) = (&base.value().0, &other.value().0);
+        if Presence::of(b, P::is_present) >= Presence::of(o, P::is_present) {
+            base
+        } else {
+            other
+        }
+    }
+    fn offers_value(&self) -> bool {
+        self.0.as_ref().is_some_and(P::is_present)
+    }
+}
+impl<T, P: CoalescePredicate<T>> Semigroup for CoalesceBy<T, P> {
+    fn op(base: Self, other: Self) -> Self {
+        Annotated::lift_unit_annotated_op((base, ()), (other, ()))
+    }
+}
+impl<T, P: CoalescePredicate<T>> Idempotent for CoalesceBy<T, P> {}
+impl<T, P: CoalescePredicate<T>, A> Annotate<A> for CoalesceBy<T, P> {
+    type Annotation = A;
+    fn annotated(self, annotation: Self::Annotation) -> Annotated<Self, A> {
+        Annotated::new(self, annotation)
+    }
+}
+impl<T, P> From<Option<T>> for CoalesceBy<T, P> {
+    fn from(value: Option<T>) -> Self {
+        Self::new(value)
+    }
+}
+impl<T, P> Deref for CoalesceBy<T, P> {
+    type Target = Option<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<T, P> DerefMut for CoalesceBy<T, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<T, P: CoalescePredicate<T>> Construction<Option<T>> for CoalesceBy<T, P> {
+    fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+impl<T, P: CoalescePredicate<T>, A> ConstructionAnnotated<Option<T>, A> for CoalesceBy<T, P> {}
+#[cfg(feature = "monoid")]
+impl<T, P: CoalescePredicate<T>> crate::Monoid for CoalesceBy<T, P> {
+    fn unit() -> Self {
+        Self::new(None)
+    }
+}
+#[cfg(feature = "monoid")]
+impl<T, P: CoalescePredicate<T>> crate::ConstructionMonoid<Option<T>> for CoalesceBy<T, P> {}
+
+/// Coalesce takes the first value of the highest [`Presence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Presence {
+    None,
+    Absent,
+    Present,
+}
+impl Presence {
+    pub(crate) fn of<T>(value: &Option<T>, is_present: impl FnOnce(&T) -> bool) -> Self {
+        match value {
+            None => Self::None,
+            Some(v) if is_present(v) => Self::Present,
+            Some(_) => Self::Absent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_idempotent, assert_monoid, assert_semigroup, Monoid};
+
+    use super::*;
+
+    struct Even;
+    impl CoalescePredicate<i32> for Even {
+        fn is_present(value: &i32) -> bool {
+            value % 2 == 0
+        }
+    }
+    type CoalesceEven = CoalesceBy<i32, Even>;
+
+    #[test]
+    fn test_coalesce_by_as_semigroup() {
+        let [a, b, c] = [Some(1), Some(2), None].map(CoalesceEven::new);
+        assert_semigroup!(a, b, c);
+        let [a, b, c] = [Some(1), Some(3), Some(4)].map(CoalesceEven::new);
+        assert_semigroup!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_by_as_monoid() {
+        let [a, b, c] = [None, Some(1), Some(2)].map(CoalesceEven::new);
+        assert_monoid!(a, b, c);
+        assert_eq!(CoalesceEven::unit(), CoalesceEven::new(None));
+    }
+
+    #[test]
+    fn test_coalesce_by_idempotent() {
+        let [a, b, c] = [Some(1), Some(2), None].map(CoalesceEven::new);
+        assert_idempotent!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_by() {
+        let [a, b, c] = [Some(1), Some(2), Some(3)].map(CoalesceEven::new);
+        assert_eq!(a.semigroup(b).into_inner(), Some(2));
+        assert_eq!(a.semigroup(c).into_inner(), Some(1));
+        assert_eq!(CoalesceEven::new(None).semigroup(c).into_inner(), Some(3));
+    }
+
+    #[test]
+    fn test_coalesce_by_without_predicate_bounds() {
+        let a = CoalesceEven::new(Some(2));
+        let b = a;
+        assert_eq!(a, b.clone());
+        assert_eq!(format!("{a:?}"), "CoalesceBy(Some(2))");
+        assert!(CoalesceEven::default() < a);
+    }
+}
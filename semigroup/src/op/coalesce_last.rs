@@ -0,0 +1,88 @@
+use semigroup_derive::{properties_priv, ConstructionPriv};
+
+use crate::{Annotated, AnnotatedSemigroup};
+
+/// A semigroup construction that returns the last non-`None` value.
+/// # Properties
+/// <!-- properties -->
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::CoalesceLast, Construction, Semigroup};
+///
+/// let a = CoalesceLast(Some(1));
+/// let b = CoalesceLast(Some(2));
+/// let c = CoalesceLast(None);
+///
+/// assert_eq!(a.semigroup(b).semigroup(c).into_inner(), Some(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[construction(annotated, monoid, idempotent, unit = Self(None))]
+#[properties_priv(selective)]
+pub struct CoalesceLast<T>(pub Option<T>);
+impl<T, A> AnnotatedSemigroup<A> for CoalesceLast<T> {
+    fn annotated_op(base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A> {
+        match (&base.value().0, &other.value().0) {
+            (_, Some(_)) => other,
+            (_, None) => base,
+        }
+    }
+    fn offers_value(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_idempotent, assert_monoid, assert_semigroup, Construction, Semigroup};
+
+    use super::*;
+
+    #[test]
+    fn test_coalesce_last_as_semigroup() {
+        let (a, b, c) = (
+            CoalesceLast(Some(1)),
+            CoalesceLast(None),
+            CoalesceLast(Some(3)),
+        );
+        assert_semigroup!(a, b, c);
+        let (a, b, c) = (
+            CoalesceLast::<u32>(None),
+            CoalesceLast(None),
+            CoalesceLast(None),
+        );
+        assert_semigroup!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_last_as_monoid() {
+        let (a, b, c) = (
+            CoalesceLast(Some(1)),
+            CoalesceLast(Some(2)),
+            CoalesceLast(None),
+        );
+        assert_monoid!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_last_idempotent() {
+        let (a, b, c) = (
+            CoalesceLast(Some(1)),
+            CoalesceLast(None),
+            CoalesceLast(Some(3)),
+        );
+        assert_idempotent!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_last() {
+        let (a, b) = (CoalesceLast(None), CoalesceLast(Some("value")));
+        assert_eq!(a.semigroup(b).into_inner(), Some("value"));
+        assert_eq!(b.semigroup(a).into_inner(), Some("value"));
+
+        let (a, b) = (CoalesceLast(Some(1)), CoalesceLast(Some(2)));
+        assert_eq!(a.semigroup(b).into_inner(), Some(2));
+        assert_eq!(b.semigroup(a).into_inner(), Some(1));
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use semigroup_derive::{properties_priv, ConstructionPriv};
+
+use crate::{op::coalesce_by::Presence, Annotated, AnnotatedSemigroup};
+
+/// A value that can be empty, such as strings and collections.
+pub trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+/// A semigroup construction that returns the first non-empty value, `Some("")` and `Some(vec![])` are treated as absent.
+/// If all values are empty, returns the first non-`None` value.
+/// # Properties
+/// <!-- properties -->
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::CoalesceNonEmpty, Construction, Semigroup};
+///
+/// let a = CoalesceNonEmpty(Some(""));
+/// let b = CoalesceNonEmpty(None);
+/// let c = CoalesceNonEmpty(Some("value"));
+///
+/// assert_eq!(a.semigroup(b).into_inner(), Some(""));
+/// assert_eq!(a.semigroup(b).semigroup(c).into_inner(), Some("value"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[construction(annotated, monoid, idempotent, unit = Self(None))]
+#[properties_priv(selective)]
+pub struct CoalesceNonEmpty<T: IsEmpty>(pub Option<T>);
+impl<T: IsEmpty, A> AnnotatedSemigroup<A> for CoalesceNonEmpty<T> {
+    fn annotated_op(base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A> {
+        let is_present = |v: &T| !v.is_empty();
+        let (b, o) = (&base.value().0, &other.value().0);
+        if Presence::of(b, is_present) >= Presence::of(o, is_present) {
+            base
+        } else {
+            other
+        }
+    }
+    fn offers_value(&self) -> bool {
+        self.0.as_ref().is_some_and(|v| !v.is_empty())
+    }
+}
+
+impl<T: IsEmpty + ?Sized> IsEmpty for &T {
+    fn is_empty(&self) -> bool {
+        T::is_empty(self)
+    }
+}
+impl IsEmpty for str {
+    fn is_empty(&self) -> bool {
+        str::is_empty(self)
+    }
+}
+impl IsEmpty for String {
+    fn is_empty(&self) -> bool {
+        String::is_empty(self)
+    }
+}
+impl<T> IsEmpty for [T] {
+    fn is_empty(&self) -> bool {
+        <[T]>::is_empty(self)
+    }
+}
+impl<T> IsEmpty for Vec<T> {
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+impl<T> IsEmpty for VecDeque<T> {
+    fn is_empty(&self) -> bool {
+        VecDeque::is_empty(self)
+    }
+}
+impl<K, V, S> IsEmpty for HashMap<K, V, S> {
+    fn is_empty(&self) -> bool {
+        HashMap::is_empty(self)
+    }
+}
+impl<K, V> IsEmpty for BTreeMap<K, V> {
+    fn is_empty(&self) -> bool {
+        BTreeMap::is_empty(self)
+    }
+}
+impl<T, S> IsEmpty for HashSet<T, S> {
+    fn is_empty(&self) -> bool {
+        HashSet::is_empty(self)
+    }
+}
+impl<T> IsEmpty for BTreeSet<T> {
+    fn is_empty(&self) -> bool {
+        BTreeSet::is_empty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_idempotent, assert_monoid, assert_semigroup, Construction, Semigroup};
+
+    use super::*;
+
+    #[test]
+    fn test_coalesce_non_empty_as_semigroup() {
+        let (a, b, c) = (
+            CoalesceNonEmpty(Some("")),
+            CoalesceNonEmpty(None),
+            CoalesceNonEmpty(Some("c")),
+        );
+        assert_semigroup!(a, b, c);
+        let (a, b, c) = (
+            CoalesceNonEmpty(Some(vec![])),
+            CoalesceNonEmpty(Some(vec![1])),
+            CoalesceNonEmpty(Some(vec![2])),
+        );
+        assert_semigroup!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_non_empty_as_monoid() {
+        let (a, b, c) = (
+            CoalesceNonEmpty(Some(String::new())),
+            CoalesceNonEmpty(None),
+            CoalesceNonEmpty(Some("c".to_string())),
+        );
+        assert_monoid!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_non_empty_idempotent() {
+        let (a, b, c) = (
+            CoalesceNonEmpty(Some("")),
+            CoalesceNonEmpty(None),
+            CoalesceNonEmpty(Some("c")),
+        );
+        assert_idempotent!(a, b, c);
+    }
+
+    #[test]
+    fn test_coalesce_non_empty() {
+        let (a, b) = (CoalesceNonEmpty(Some("")), CoalesceNonEmpty(Some("value")));
+        assert_eq!(a.semigroup(b).into_inner(), Some("value"));
+        assert_eq!(b.semigroup(a).into_inner(), Some("value"));
+
+        let (a, b) = (
+            CoalesceNonEmpty(None),
+            CoalesceNonEmpty(Some(BTreeSet::<u8>::new())),
+        );
+        assert_eq!(
+            a.clone().semigroup(b.clone()).into_inner(),
+            Some(BTreeSet::new())
+        );
+        assert_eq!(b.semigroup(a).into_inner(), Some(BTreeSet::new()));
+    }
+}
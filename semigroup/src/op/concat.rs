@@ -1,6 +1,6 @@
 use semigroup_derive::{properties_priv, ConstructionPriv};
 
-use crate::{Annotated, AnnotatedSemigroup};
+use crate::{Annotated, AnnotatedSemigroup, IntoOwned};
 
 /// A semigroup construction that concatenates two values.
 /// # Properties
@@ -26,7 +26,7 @@ use crate::{Annotated, AnnotatedSemigroup};
     unit_annotation = "vec![(); 0]",
     without_annotate_impl
 )]
-#[properties_priv(annotated, monoid)]
+#[properties_priv]
 pub struct Concat<T: IntoIterator + FromIterator<T::Item>>(pub T);
 impl<T: IntoIterator + FromIterator<T::Item>, A: IntoIterator + FromIterator<A::Item>>
     AnnotatedSemigroup<A> for Concat<T>
@@ -69,6 +69,15 @@ where
         }
     }
 }
+impl<T: IntoIterator + FromIterator<T::Item> + IntoOwned> IntoOwned for Concat<T>
+where
+    T::Owned: IntoIterator + FromIterator<<T::Owned as IntoIterator>::Item>,
+{
+    type Owned = Concat<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Concat(self.0.into_owned())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+
+use semigroup_derive::properties_priv;
+
+use crate::{Commutative, Semigroup};
+
+/// A semigroup construction merging two DDSketches, a quantile sketch of floating-point values with relative error bounds.
+/// - count, min, max
+/// - quantile
+///
+/// Unlike [`crate::op::HdrHistogram`], negative and fractional values are also supported.
+/// The relative error of quantiles is bounded by `ACCURACY_PERMILLE / 1000`, 1% by default.
+/// `NaN` and infinite values are ignored.
+///
+/// Values are counted in logarithmic buckets, so merging DDSketches is exactly associative and commutative.
+/// # Properties
+/// <!-- properties -->
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::DDSketch, Semigroup};
+///
+/// let a: DDSketch = (1..=500).map(|x| -f64::from(x)).collect();
+/// let b: DDSketch = (1..=500).map(f64::from).collect();
+///
+/// let sketch = a.semigroup(b);
+/// assert_eq!(sketch.count(), 1000);
+/// assert_eq!(sketch.min(), Some(-500.0));
+/// assert!((sketch.quantile(0.9).unwrap() - 400.0).abs() <= 400.0 * 0.01);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[properties_priv(monoid, commutative, unit = Self::default())]
+pub struct DDSketch<const ACCURACY_PERMILLE: u32 = 10> {
+    positive: BTreeMap<i32, u64>,
+    negative: BTreeMap<i32, u64>,
+    zero: u64,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+impl<const ACCURACY_PERMILLE: u32> Semigroup for DDSketch<ACCURACY_PERMILLE> {
+    fn op(mut base: Self, other: Self) -> Self {
+        if base.count == 0 {
+            return other;
+        } else if other.count == 0 {
+            return base;
+        }
+        for (index, count) in other.positive {
+            *base.positive.entry(index).or_default() += count;
+        }
+        for (index, count) in other.negative {
+            *base.negative.entry(index).or_default() += count;
+        }
+        base.zero += other.zero;
+        base.count += other.count;
+        base.min = base.min.min(other.min);
+        base.max = base.max.max(other.max);
+        base
+    }
+}
+impl<const ACCURACY_PERMILLE: u32> Commutative for DDSketch<ACCURACY_PERMILLE> {}
+#[cfg(feature = "monoid")]
+impl<const ACCURACY_PERMILLE: u32> crate::Monoid for DDSketch<ACCURACY_PERMILLE> {
+    fn unit() -> Self {
+        Self::default()
+    }
+}
+impl<const ACCURACY_PERMILLE: u32> From<f64> for DDSketch<ACCURACY_PERMILLE> {
+    fn from(value: f64) -> Self {
+        Self::from_iter([value])
+    }
+}
+impl<const ACCURACY_PERMILLE: u32> FromIterator<f64> for DDSketch<ACCURACY_PERMILLE> {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut sketch = Self::default();
+        for value in iter.into_iter().filter(|v| v.is_finite()) {
+            if value.abs() < f64::MIN_POSITIVE {
+                sketch.zero += 1;
+            } else if value > 0.0 {
+                *sketch.positive.entry(Self::index(value)).or_default() += 1;
+            } else {
+                *sketch.negative.entry(Self::index(-value)).or_default() += 1;
+            }
+            (sketch.min, sketch.max) = if sketch.count == 0 {
+                (value, value)
+            } else {
+                (sketch.min.min(value), sketch.max.max(value))
+            };
+            sketch.count += 1;
+        }
+        sketch
+    }
+}
+impl<const ACCURACY_PERMILLE: u32> DDSketch<ACCURACY_PERMILLE> {
+    /// The relative accuracy of quantiles.
+    pub fn relative_accuracy() -> f64 {
+        const { assert!(0 < ACCURACY_PERMILLE && ACCURACY_PERMILLE < 1000) };
+        ACCURACY_PERMILLE as f64 / 1000.0
+    }
+    /// The number of values.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// The minimum value, `None` if there are no values.
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+    /// The maximum value, `None` if there are no values.
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+    /// The estimated value at the quantile `q` in `[0, 1]`, `None` if there are no values.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = q.clamp(0.0, 1.0) * (self.count - 1) as f64;
+        let negative = self
+            .negative
+            .iter()
+            .rev()
+            .map(|(&i, &c)| (-Self::value(i), c));
+        let zero = [(0.0, self.zero)];
+        let positive = self.positive.iter().map(|(&i, &c)| (Self::value(i), c));
+
+        let mut cumulative = 0;
+        negative
+            .chain(zero)
+            .chain(positive)
+            .find(|&(_, count)| {
+                cumulative += count;
+                cumulative as f64 > rank
+            })
+            .map(|(value, _)| value.clamp(self.min, self.max))
+    }
+
+    fn gamma() -> f64 {
+        let alpha = Self::relative_accuracy();
+        (1.0 + alpha) / (1.0 - alpha)
+    }
+    fn index(value: f64) -> i32 {
+        (value.ln() / Self::gamma().ln()).ceil() as i32
+    }
+    fn value(index: i32) -> f64 {
+        let gamma = Self::gamma();
+        2.0 * gamma.powi(index) / (gamma + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_commutative, assert_monoid, assert_semigroup, Monoid, Semigroup};
+
+    use super::*;
+
+    #[test]
+    fn test_dd_sketch_as_semigroup() {
+        let a: DDSketch = [1.0, 2.5, -3.0].into_iter().collect();
+        let b: DDSketch = [0.0, 1e-3, 1e6].into_iter().collect();
+        let c: DDSketch = [-42.0].into_iter().collect();
+        assert_semigroup!(a, b, c);
+    }
+
+    #[test]
+    fn test_dd_sketch_as_monoid() {
+        let a: DDSketch = [1.0, 2.5, -3.0].into_iter().collect();
+        let b: DDSketch = [0.0, 1e-3, 1e6].into_iter().collect();
+        let c: DDSketch = [-42.0].into_iter().collect();
+        assert_monoid!(a, b, c);
+    }
+
+    #[test]
+    fn test_dd_sketch_commutative() {
+        let a: DDSketch = [1.0, 2.5, -3.0].into_iter().collect();
+        let b: DDSketch = [0.0, 1e-3, 1e6].into_iter().collect();
+        let c: DDSketch = [-42.0].into_iter().collect();
+        assert_commutative!(a, b, c);
+    }
+
+    #[test]
+    fn test_dd_sketch() {
+        let a: DDSketch = (1..=5000).map(f64::from).collect();
+        let b: DDSketch = (5001..=10000).map(f64::from).collect();
+
+        let sketch = a.semigroup(b);
+        assert_eq!(sketch.count(), 10000);
+        assert_eq!(sketch.min(), Some(1.0));
+        assert_eq!(sketch.max(), Some(10000.0));
+        for q in [0.0, 0.01, 0.1, 0.5, 0.9, 0.99, 1.0] {
+            let expected = 1.0 + q * 9999.0;
+            let estimated = sketch.quantile(q).unwrap();
+            assert!(
+                (estimated - expected).abs() <= expected * 0.01 + 1.0,
+                "{q}: {estimated}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dd_sketch_relative_accuracy() {
+        let values = [-1e9, -0.5, 1e-6, 3.0, 7e12];
+        for value in values {
+            let sketch = DDSketch::<5>::from(value);
+            let estimated = sketch.quantile(0.5).unwrap();
+            assert!((estimated - value).abs() <= value.abs() * 0.005);
+        }
+        let sketch: DDSketch<5> = values.into_iter().collect();
+        let estimated = sketch.quantile(0.25).unwrap();
+        assert!((estimated + 0.5).abs() <= 0.5 * 0.005);
+    }
+
+    #[test]
+    fn test_dd_sketch_non_finite() {
+        let sketch: DDSketch = [f64::NAN, f64::INFINITY, 0.0].into_iter().collect();
+        assert_eq!(sketch.count(), 1);
+        assert_eq!(sketch.quantile(0.5), Some(0.0));
+        assert_eq!(DDSketch::<10>::from(f64::NAN), DDSketch::unit());
+        assert_eq!(DDSketch::<10>::unit().quantile(0.5), None);
+    }
+}
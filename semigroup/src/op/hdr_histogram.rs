@@ -1,4 +1,12 @@
-use hdrhistogram::{Counter, Histogram};
+use std::fmt::{self, Display};
+
+use hdrhistogram::{
+    serialization::{
+        DeserializeError, Deserializer, Serializer, V2DeflateSerializeError, V2DeflateSerializer,
+        V2SerializeError, V2Serializer,
+    },
+    Counter, Histogram,
+};
 use semigroup_derive::{properties_priv, ConstructionPriv};
 
 use crate::Semigroup;
@@ -7,6 +15,18 @@ use crate::Semigroup;
 /// - mean
 /// - quantile
 /// - and more...
+///
+/// The shape of the histogram is carried in the type, so [`HdrHistogram::base_histogram`], `unit`, [`From<u64>`]
+/// and [`FromIterator<u64>`] always produce histograms that can be merged with each other.
+/// - `SIGFIG`: the number of significant decimal digits, in `0..=5`. 3 by default.
+/// - `LOW`: the lowest discernible value, at least 1. 1 by default.
+/// - `HIGH`: the highest trackable value, at least `2 * LOW`. 0 by default, which means auto-resizing without bound.
+///
+/// Values beyond the bounds of a bounded histogram are saturated to the bounds.
+///
+/// Histograms can be encoded in the V2 or V2 + DEFLATE format of HdrHistogram by [`HdrHistogram::encode`]
+/// and [`HdrHistogram::encode_deflate`], so partial histograms can be shipped and merged elsewhere.
+/// With the `serde` feature, it is serialized as bytes of the V2 + DEFLATE format.
 /// # Properties
 /// <!-- properties -->
 ///
@@ -21,41 +41,213 @@ use crate::Semigroup;
 /// assert_eq!(h.mean(), 3.5);
 /// assert_eq!(h.value_at_quantile(0.9), 6);
 /// ```
+///
+/// Bounded histogram with 2 significant digits.
+/// ```
+/// use semigroup::{op::HdrHistogram, Construction, Semigroup};
+///
+/// let a: HdrHistogram<u32, 2, 1, 1000> = [1u64, 2, 3].into_iter().collect();
+/// let b: HdrHistogram<u32, 2, 1, 1000> = [4, 5, 6000].into_iter().collect();
+///
+/// let h = a.semigroup(b);
+/// assert_eq!(h.sigfig(), 2);
+/// assert_eq!(h.max(), h.highest_equivalent(1000));
+/// ```
+///
+/// Histograms of different shapes cannot be merged.
+/// ```compile_fail
+/// use semigroup::{op::HdrHistogram, Semigroup};
+///
+/// let a: HdrHistogram<u32, 2> = [1u64, 2, 3].into_iter().collect();
+/// let b: HdrHistogram<u32, 3> = [4, 5, 6].into_iter().collect();
+///
+/// let h = a.semigroup(b);
+/// ```
 #[derive(Debug, Clone, PartialEq, ConstructionPriv)]
 #[construction(monoid, commutative, unit = Self(Self::base_histogram()))]
-#[properties_priv(monoid, commutative)]
-pub struct HdrHistogram<T: Counter>(pub Histogram<T>);
-impl<T: Counter> Semigroup for HdrHistogram<T> {
+#[properties_priv]
+pub struct HdrHistogram<T: Counter, const SIGFIG: u8 = 3, const LOW: u64 = 1, const HIGH: u64 = 0>(
+    pub Histogram<T>,
+);
+impl<T: Counter, const SIGFIG: u8, const LOW: u64, const HIGH: u64> Semigroup
+    for HdrHistogram<T, SIGFIG, LOW, HIGH>
+{
     fn op(mut base: Self, other: Self) -> Self {
         base.0 += other.0;
         base
     }
 }
-impl<T: Counter> From<u64> for HdrHistogram<T> {
+impl<T: Counter, const SIGFIG: u8, const LOW: u64, const HIGH: u64> From<u64>
+    for HdrHistogram<T, SIGFIG, LOW, HIGH>
+{
     fn from(value: u64) -> Self {
-        let mut h = Self::base_histogram();
-        h += value;
-        Self(h)
+        Self::from_iter([value])
     }
 }
-impl<T: Counter> FromIterator<u64> for HdrHistogram<T> {
+impl<T: Counter, const SIGFIG: u8, const LOW: u64, const HIGH: u64> FromIterator<u64>
+    for HdrHistogram<T, SIGFIG, LOW, HIGH>
+{
     fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
         let mut h = Self::base_histogram();
         for v in iter {
-            h += v;
+            if HIGH == 0 {
+                h += v;
+            } else {
+                h.saturating_record(v);
+            }
         }
         Self(h)
     }
 }
-impl<T: Counter> HdrHistogram<T> {
+impl<T: Counter, const SIGFIG: u8, const LOW: u64, const HIGH: u64>
+    HdrHistogram<T, SIGFIG, LOW, HIGH>
+{
+    /// The empty histogram of this shape. Invalid shapes are rejected at compile time.
     pub fn base_histogram() -> Histogram<T> {
-        Histogram::new(3).unwrap_or_else(|_| unreachable!())
+        const {
+            assert!(SIGFIG <= 5, "SIGFIG must be in 0..=5");
+            assert!(
+                1 <= LOW && LOW <= u64::MAX / 2,
+                "LOW must be in 1..=u64::MAX / 2"
+            );
+            assert!(
+                HIGH == 0 || 2 * LOW <= HIGH,
+                "HIGH must be 0 or at least 2 * LOW"
+            );
+            let sub_bucket_half_count_magnitude =
+                (2 * 10u64.pow(SIGFIG as u32)).next_power_of_two().ilog2() - 1;
+            assert!(
+                LOW.ilog2() + sub_bucket_half_count_magnitude <= 61,
+                "SIGFIG cannot be represented beyond LOW"
+            );
+        };
+        if HIGH == 0 {
+            let mut h =
+                Histogram::new_with_bounds(LOW, 2 * LOW, SIGFIG).unwrap_or_else(|_| unreachable!());
+            h.auto(true);
+            h
+        } else {
+            Histogram::new_with_bounds(LOW, HIGH, SIGFIG).unwrap_or_else(|_| unreachable!())
+        }
+    }
+
+    /// Encode the histogram in the V2 format of HdrHistogram.
+    /// # Examples
+    /// ```
+    /// use semigroup::{op::HdrHistogram, Semigroup};
+    ///
+    /// let worker1: HdrHistogram<u32> = [1u64, 2, 3].into_iter().collect();
+    /// let worker2: HdrHistogram<u32> = [4, 5, 6].into_iter().collect();
+    /// let (bytes1, bytes2) = (worker1.encode().unwrap(), worker2.encode().unwrap());
+    ///
+    /// let h1 = HdrHistogram::<u32>::decode(&bytes1).unwrap();
+    /// let h2 = HdrHistogram::<u32>::decode(&bytes2).unwrap();
+    /// assert_eq!(h1.semigroup(h2), worker1.semigroup(worker2));
+    /// ```
+    pub fn encode(&self) -> Result<Vec<u8>, V2SerializeError> {
+        let mut buf = Vec::new();
+        V2Serializer::new().serialize(&self.0, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Encode the histogram in the V2 + DEFLATE format of HdrHistogram, which is usually smaller than [`HdrHistogram::encode`].
+    pub fn encode_deflate(&self) -> Result<Vec<u8>, V2DeflateSerializeError> {
+        let mut buf = Vec::new();
+        V2DeflateSerializer::new().serialize(&self.0, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Decode the histogram encoded in either the V2 or V2 + DEFLATE format.
+    /// The encoded histogram must have the same shape as this type, otherwise it cannot be merged.
+    pub fn decode(mut bytes: &[u8]) -> Result<Self, HdrHistogramDecodeError> {
+        let mut h: Histogram<T> = Deserializer::new()
+            .deserialize(&mut bytes)
+            .map_err(HdrHistogramDecodeError::Deserialize)?;
+        let base = Self::base_histogram();
+        if h.sigfig() != base.sigfig()
+            || h.low() != base.low()
+            || HIGH != 0 && h.high() != base.high()
+        {
+            let (sigfig, low, high) = (h.sigfig(), h.low(), h.high());
+            return Err(HdrHistogramDecodeError::IncompatibleShape { sigfig, low, high });
+        }
+        h.auto(HIGH == 0);
+        Ok(Self(h))
+    }
+}
+#[cfg(feature = "serde")]
+impl<T: Counter, const SIGFIG: u8, const LOW: u64, const HIGH: u64> serde::Serialize
+    for HdrHistogram<T, SIGFIG, LOW, HIGH>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.encode_deflate().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, T: Counter, const SIGFIG: u8, const LOW: u64, const HIGH: u64> serde::Deserialize<'de>
+    for HdrHistogram<T, SIGFIG, LOW, HIGH>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+        impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("bytes of an encoded histogram")
+            }
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(v.to_vec())
+            }
+            fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(v)
+            }
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(b) = seq.next_element()? {
+                    bytes.push(b);
+                }
+                Ok(bytes)
+            }
+        }
+        let bytes = deserializer.deserialize_byte_buf(BytesVisitor)?;
+        Self::decode(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error of [`HdrHistogram::decode`].
+#[derive(Debug)]
+pub enum HdrHistogramDecodeError {
+    /// The bytes are not a histogram encoded in the V2 or V2 + DEFLATE format.
+    Deserialize(DeserializeError),
+    /// The encoded histogram has a different shape from the type.
+    IncompatibleShape { sigfig: u8, low: u64, high: u64 },
+}
+impl Display for HdrHistogramDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deserialize(e) => write!(f, "{e}"),
+            Self::IncompatibleShape { sigfig, low, high } => write!(
+                f,
+                "incompatible histogram: sigfig {sigfig}, low {low}, high {high}"
+            ),
+        }
+    }
+}
+impl std::error::Error for HdrHistogramDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialize(e) => Some(e),
+            Self::IncompatibleShape { .. } => None,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_commutative, assert_monoid, assert_semigroup, Semigroup};
+    use crate::{assert_commutative, assert_monoid, assert_semigroup, Monoid, Semigroup};
 
     use super::*;
 
@@ -104,4 +296,89 @@ mod tests {
         assert_eq!(res.value_at_quantile(0.5), 3);
         assert_eq!(res.value_at_quantile(0.9), 6);
     }
+
+    #[test]
+    fn test_hdr_histogram_shape() {
+        let default: HdrHistogram<u32> = HdrHistogram::unit();
+        assert_eq!(default.sigfig(), 3);
+        assert_eq!(default.low(), 1);
+        assert!(default.is_auto_resize());
+        assert_eq!(default.0, Histogram::<u32>::new(3).unwrap());
+
+        let precise: HdrHistogram<u64, 5, 1000> = [1000u64, 1_000_000_000].into_iter().collect();
+        assert_eq!(precise.sigfig(), 5);
+        assert_eq!(precise.low(), 1000);
+        assert_eq!(precise.max(), precise.highest_equivalent(1_000_000_000));
+
+        let bounded: HdrHistogram<u16, 2, 1, 100> = HdrHistogram::unit();
+        assert!(!bounded.is_auto_resize());
+        assert_eq!(bounded.high(), 100);
+        assert_eq!(bounded.clone().semigroup(bounded.clone()), bounded);
+    }
+
+    #[test]
+    fn test_hdr_histogram_bounded_saturates() {
+        let a: HdrHistogram<u32, 2, 10, 100> = [0u64, 50].into_iter().collect();
+        let b: HdrHistogram<u32, 2, 10, 100> = HdrHistogram::from(1_000_000);
+
+        let res = a.semigroup(b);
+        assert_eq!(res.len(), 3);
+        assert_eq!(res.min(), 0);
+        assert_eq!(res.max(), res.highest_equivalent(100));
+        assert_eq!(res.high(), 100);
+    }
+
+    #[test]
+    fn test_hdr_histogram_encode_decode() {
+        let a: HdrHistogram<u32> = (0..100_000).step_by(7).collect();
+        let b: HdrHistogram<u32> = [1u64, 2, 3].into_iter().collect();
+
+        let (bytes, deflated) = (a.encode().unwrap(), a.encode_deflate().unwrap());
+        assert!(deflated.len() < bytes.len());
+        for bytes in [bytes, deflated] {
+            let decoded = HdrHistogram::<u32>::decode(&bytes).unwrap();
+            assert_eq!(decoded, a);
+            assert!(decoded.is_auto_resize());
+            assert_eq!(decoded.semigroup(b.clone()), a.clone().semigroup(b.clone()));
+        }
+
+        let bounded: HdrHistogram<u32, 2, 1, 1000> = [1u64, 2, 3].into_iter().collect();
+        let decoded = HdrHistogram::<u32, 2, 1, 1000>::decode(&bounded.encode().unwrap()).unwrap();
+        assert_eq!(decoded, bounded);
+        assert!(!decoded.is_auto_resize());
+        assert_eq!(decoded.high(), 1000);
+
+        assert!(matches!(
+            HdrHistogram::<u32, 3>::decode(&bounded.encode().unwrap()),
+            Err(HdrHistogramDecodeError::IncompatibleShape {
+                sigfig: 2,
+                low: 1,
+                high: 1000
+            })
+        ));
+        assert!(matches!(
+            HdrHistogram::<u32, 2, 1, 2000>::decode(&bounded.encode().unwrap()),
+            Err(HdrHistogramDecodeError::IncompatibleShape { .. })
+        ));
+        assert!(matches!(
+            HdrHistogram::<u32>::decode(b"invalid"),
+            Err(HdrHistogramDecodeError::Deserialize(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_hdr_histogram_serde() {
+        let h: HdrHistogram<u64> = (1..1000).collect();
+        let json = serde_json::to_string(&h).unwrap();
+        let deserialized: HdrHistogram<u64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, h);
+        assert_eq!(
+            deserialized.value_at_quantile(0.5),
+            h.value_at_quantile(0.5)
+        );
+
+        assert!(serde_json::from_str::<HdrHistogram<u64, 2>>(&json).is_err());
+        assert!(serde_json::from_str::<HdrHistogram<u64>>("[0,1,2]").is_err());
+    }
 }
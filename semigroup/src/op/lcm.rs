@@ -18,8 +18,8 @@ use crate::Semigroup;
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[construction(monoid, commutative, unit = Self(T::one()))]
-#[properties_priv(monoid, commutative)]
+#[construction(monoid, commutative, idempotent, unit = Self(T::one()))]
+#[properties_priv]
 pub struct Lcm<T: Unsigned + Integer + Clone>(pub T);
 impl<T: Unsigned + Integer + Clone> Semigroup for Lcm<T> {
     fn op(base: Self, other: Self) -> Self {
@@ -29,7 +29,10 @@ impl<T: Unsigned + Integer + Clone> Semigroup for Lcm<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_commutative, assert_monoid, assert_semigroup, Construction, Semigroup};
+    use crate::{
+        assert_commutative, assert_idempotent, assert_monoid, assert_semigroup, Construction,
+        Semigroup,
+    };
 
     use super::*;
 
@@ -51,6 +54,12 @@ mod tests {
         assert_commutative!(a, b, c);
     }
 
+    #[test]
+    fn test_lcm_idempotent() {
+        let (a, b, c) = (Lcm(4u32), Lcm(6), Lcm(9));
+        assert_idempotent!(a, b, c);
+    }
+
     #[test]
     fn test_lcm() {
         let (a, b) = (Lcm(12u32), Lcm(18));
@@ -17,8 +17,8 @@ use crate::{Annotated, AnnotatedSemigroup};
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[construction(annotated, monoid, commutative, unit = Self(T::min_value()), unit_where = "T: num::Bounded")]
-#[properties_priv(annotated, monoid, commutative)]
+#[construction(annotated, monoid, commutative, idempotent, unit = Self(T::min_value()), unit_where = "T: num::Bounded")]
+#[properties_priv(selective)]
 pub struct Max<T: Ord>(pub T);
 impl<A, T: Ord> AnnotatedSemigroup<A> for Max<T> {
     fn annotated_op(base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A> {
@@ -28,7 +28,10 @@ impl<A, T: Ord> AnnotatedSemigroup<A> for Max<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_commutative, assert_monoid, assert_semigroup, Construction, Semigroup};
+    use crate::{
+        assert_commutative, assert_idempotent, assert_monoid, assert_semigroup, Construction,
+        Semigroup,
+    };
 
     use super::*;
 
@@ -50,6 +53,12 @@ mod tests {
         assert_commutative!(a, b, c);
     }
 
+    #[test]
+    fn test_max_idempotent() {
+        let (a, b, c) = (Max(1), Max(2), Max(3));
+        assert_idempotent!(a, b, c);
+    }
+
     #[test]
     fn test_max() {
         let (a, b) = (Max(1), Max(2));
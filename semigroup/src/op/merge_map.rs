@@ -0,0 +1,326 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+use semigroup_derive::{properties_priv, ConstructionPriv};
+
+use crate::{Annotate, Annotated, AnnotatedSemigroup, Commutative, Idempotent, Semigroup};
+
+/// A semigroup construction that merges two maps key by key,
+/// values of the same key are operated by their [`Semigroup`].
+/// Keys only in `other` are appended after the keys of `base`, so insertion ordered maps such as `IndexMap` keep their order.
+///
+/// # Annotation
+/// [`MergeMap`] is annotated per key, so its annotation is a map from the key to the annotation of the value.
+/// [`Annotate::annotated`] gives every key an annotation. If an annotation built by hand lacks a key,
+/// the values of the key are operated by [`Semigroup::op`] and the key has no annotation, because which side won is unknown.
+///
+/// # Properties
+/// <!-- properties -->
+///
+/// [`MergeMap`] is also [`Commutative`] and [`Idempotent`] if the value is.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use semigroup::{op::{MergeMap, Overwrite}, Construction, Semigroup};
+///
+/// let a = MergeMap(HashMap::from([("accept", Overwrite("*/*")), ("host", Overwrite("a"))]));
+/// let b = MergeMap(HashMap::from([("host", Overwrite("b")), ("user-agent", Overwrite("c"))]));
+///
+/// let expected = HashMap::from([
+///     ("accept", Overwrite("*/*")),
+///     ("host", Overwrite("b")),
+///     ("user-agent", Overwrite("c")),
+/// ]);
+/// assert_eq!(a.semigroup(b).into_inner(), expected);
+/// ```
+///
+/// Used as a field of [`Semigroup`] derive.
+/// ```
+/// use std::collections::BTreeMap;
+/// use semigroup::{op::{Coalesce, Sum}, Semigroup};
+///
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// struct Config {
+///     #[semigroup(with = "semigroup::op::MergeMap")]
+///     labels: BTreeMap<String, Coalesce<String>>,
+///     #[semigroup(with = "semigroup::op::MergeMap")]
+///     quota: BTreeMap<String, Sum<u32>>,
+/// }
+///
+/// let file = Config {
+///     labels: BTreeMap::from([("app".to_string(), Coalesce(Some("web".to_string())))]),
+///     quota: BTreeMap::from([("cpu".to_string(), Sum(2))]),
+/// };
+/// let env = Config {
+///     labels: BTreeMap::from([("env".to_string(), Coalesce(Some("prod".to_string())))]),
+///     quota: BTreeMap::from([("cpu".to_string(), Sum(1)), ("gpu".to_string(), Sum(1))]),
+/// };
+///
+/// let config = file.semigroup(env);
+/// assert_eq!(config.labels.len(), 2);
+/// assert_eq!(config.quota["cpu"], Sum(3));
+/// assert_eq!(config.quota["gpu"], Sum(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[construction(monoid)]
+#[properties_priv(annotated)]
+pub struct MergeMap<M: MergeableMap>(pub M)
+where
+    M::Value: Semigroup;
+impl<M: MergeableMap> Semigroup for MergeMap<M>
+where
+    M::Value: Semigroup,
+{
+    fn op(base: Self, other: Self) -> Self {
+        let mut other = other.0;
+        let mut merged: M = base
+            .0
+            .into_iter()
+            .map(|(k, v)| match other.remove_key(&k) {
+                Some(o) => (k, Semigroup::op(v, o)),
+                None => (k, v),
+            })
+            .collect();
+        merged.extend(other);
+        Self(merged)
+    }
+}
+impl<M: MergeableMap> Commutative for MergeMap<M> where M::Value: Commutative {}
+impl<M: MergeableMap> Idempotent for MergeMap<M> where M::Value: Idempotent {}
+
+impl<M: MergeableMap, X: MergeableMap<Key = M::Key>> AnnotatedSemigroup<X> for MergeMap<M>
+where
+    M::Key: Clone,
+    M::Value: AnnotatedSemigroup<X::Value>,
+{
+    fn annotated_op(base: Annotated<Self, X>, other: Annotated<Self, X>) -> Annotated<Self, X> {
+        let (base_value, mut base_annotation) = base.into_parts();
+        let (other_value, mut other_annotation) = other.into_parts();
+        let mut other_value = other_value.0;
+
+        let (mut value, mut annotation) = (M::default(), X::default());
+        for (k, b) in base_value.0 {
+            let (v, a) = match (other_value.remove_key(&k), base_annotation.remove_key(&k)) {
+                (Some(o), ba) => match (ba, other_annotation.remove_key(&k)) {
+                    (Some(ba), Some(oa)) => {
+                        let (b, o) = (Annotated::new(b, ba), Annotated::new(o, oa));
+                        let (v, a) = AnnotatedSemigroup::annotated_op(b, o).into_parts();
+                        (v, Some(a))
+                    }
+                    // the winner is unknown without both annotations
+                    _ => (Semigroup::op(b, o), None),
+                },
+                (None, ba) => (b, ba),
+            };
+            annotation.extend(a.map(|a| (k.clone(), a)));
+            value.extend([(k, v)]);
+        }
+        for (k, o) in other_value {
+            let oa = other_annotation.remove_key(&k);
+            annotation.extend(oa.map(|a| (k.clone(), a)));
+            value.extend([(k, o)]);
+        }
+        Annotated::new(Self(value), annotation)
+    }
+}
+impl<M: MergeableMap, X: MergeableMap<Key = M::Key>> Annotate<X> for MergeMap<M>
+where
+    M::Key: Clone,
+    M::Value: Semigroup,
+    X::Value: Clone,
+{
+    type Annotation = X::Value;
+    fn annotated(self, annotation: Self::Annotation) -> Annotated<Self, X> {
+        let (value, annotation): (M, X) = self
+            .0
+            .into_iter()
+            .map(|(k, v)| ((k.clone(), v), (k, annotation.clone())))
+            .unzip();
+        Annotated::new(Self(value), annotation)
+    }
+}
+impl<M: MergeableMap, X: MergeableMap<Key = M::Key>> crate::ConstructionAnnotated<M, X>
+    for MergeMap<M>
+where
+    M::Key: Clone,
+    M::Value: AnnotatedSemigroup<X::Value>,
+    X::Value: Clone,
+{
+}
+#[cfg(feature = "monoid")]
+impl<M: MergeableMap, X: MergeableMap<Key = M::Key>> crate::AnnotatedMonoid<X> for MergeMap<M>
+where
+    M::Key: Clone,
+    M::Value: AnnotatedSemigroup<X::Value>,
+{
+    fn annotated_unit() -> Annotated<Self, X> {
+        Annotated::new(Self(M::default()), X::default())
+    }
+}
+
+/// A map that can be merged key by key by [`MergeMap`].
+pub trait MergeableMap:
+    Default
+    + IntoIterator<Item = (Self::Key, Self::Value)>
+    + FromIterator<(Self::Key, Self::Value)>
+    + Extend<(Self::Key, Self::Value)>
+{
+    type Key;
+    type Value;
+    /// Remove the value of `key` without changing the order of the other entries.
+    fn remove_key(&mut self, key: &Self::Key) -> Option<Self::Value>;
+}
+impl<K: Eq + Hash, V, S: BuildHasher + Default> MergeableMap for HashMap<K, V, S> {
+    type Key = K;
+    type Value = V;
+    fn remove_key(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+}
+impl<K: Ord, V> MergeableMap for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+    fn remove_key(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+}
+#[cfg(feature = "indexmap")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "indexmap")))]
+impl<K: Eq + Hash, V, S: BuildHasher + Default> MergeableMap for indexmap::IndexMap<K, V, S> {
+    type Key = K;
+    type Value = V;
+    fn remove_key(&mut self, key: &K) -> Option<V> {
+        self.shift_remove(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_commutative, assert_idempotent, assert_monoid, assert_semigroup,
+        op::{Coalesce, Concat, Overwrite, Sum},
+        Construction, Semigroup,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_merge_map_as_semigroup() {
+        let a = MergeMap(BTreeMap::from([(1, Concat(vec![1])), (2, Concat(vec![2]))]));
+        let b = MergeMap(BTreeMap::from([(2, Concat(vec![3]))]));
+        let c = MergeMap(BTreeMap::from([(1, Concat(vec![4])), (3, Concat(vec![5]))]));
+        assert_semigroup!(&[a, b, c], exhaustive);
+    }
+
+    #[test]
+    fn test_merge_map_as_monoid() {
+        let a = MergeMap(HashMap::from([("x", Sum(1)), ("y", Sum(2))]));
+        let b = MergeMap(HashMap::from([("y", Sum(3))]));
+        let c = MergeMap(HashMap::new());
+        assert_monoid!(&[a.clone(), b.clone(), c.clone()], exhaustive);
+        assert_commutative!(a, b, c);
+    }
+
+    #[test]
+    fn test_merge_map_idempotent() {
+        let a = MergeMap(BTreeMap::from([
+            (1, Coalesce(Some(1))),
+            (2, Coalesce(None)),
+        ]));
+        let b = MergeMap(BTreeMap::from([(2, Coalesce(Some(2)))]));
+        let c = MergeMap(BTreeMap::from([(3, Coalesce(Some(3)))]));
+        assert_idempotent!(a, b, c);
+    }
+
+    #[test]
+    fn test_merge_map() {
+        let a = MergeMap(BTreeMap::from([("a", Overwrite(1)), ("b", Overwrite(2))]));
+        let b = MergeMap(BTreeMap::from([("b", Overwrite(3)), ("c", Overwrite(4))]));
+        assert_eq!(
+            a.semigroup(b).into_inner(),
+            BTreeMap::from([
+                ("a", Overwrite(1)),
+                ("b", Overwrite(3)),
+                ("c", Overwrite(4))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_map_annotated() {
+        let a = MergeMap(HashMap::from([
+            ("a", Coalesce(None)),
+            ("b", Coalesce(Some(2))),
+        ]));
+        let b = MergeMap(HashMap::from([
+            ("a", Coalesce(Some(3))),
+            ("c", Coalesce(Some(4))),
+        ]));
+        let (a, b) = (a.annotated("first"), b.annotated("second"));
+        let (value, annotation): (_, HashMap<_, _>) = a.semigroup(b).into_parts();
+        assert_eq!(
+            value.into_inner(),
+            HashMap::from([
+                ("a", Coalesce(Some(3))),
+                ("b", Coalesce(Some(2))),
+                ("c", Coalesce(Some(4))),
+            ])
+        );
+        assert_eq!(
+            annotation,
+            HashMap::from([("a", "second"), ("b", "first"), ("c", "second")])
+        );
+    }
+
+    #[test]
+    fn test_merge_map_annotated_missing_key() {
+        let a = Annotated::new(
+            MergeMap(BTreeMap::from([
+                ("a", Coalesce(Some(1))),
+                ("b", Coalesce(None)),
+                ("c", Coalesce(Some(5))),
+            ])),
+            BTreeMap::from([("b", "first"), ("c", "first")]),
+        );
+        let b = Annotated::new(
+            MergeMap(BTreeMap::from([
+                ("a", Coalesce(Some(3))),
+                ("b", Coalesce(Some(4))),
+            ])),
+            BTreeMap::from([("a", "second")]),
+        );
+        let (value, annotation) = a.semigroup(b).into_parts();
+        assert_eq!(
+            value.into_inner(),
+            BTreeMap::from([
+                ("a", Coalesce(Some(1))),
+                ("b", Coalesce(Some(4))),
+                ("c", Coalesce(Some(5)))
+            ])
+        );
+        assert_eq!(annotation, BTreeMap::from([("c", "first")]));
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_merge_map_keeps_order() {
+        use indexmap::IndexMap;
+
+        let a = MergeMap(IndexMap::from([
+            ("z", Sum(1)),
+            ("a", Sum(2)),
+            ("m", Sum(3)),
+        ]));
+        let b = MergeMap(IndexMap::from([("y", Sum(10)), ("a", Sum(20))]));
+        let merged = a.semigroup(b).into_inner();
+        assert_eq!(
+            merged.keys().collect::<Vec<_>>(),
+            vec![&"z", &"a", &"m", &"y"]
+        );
+        assert_eq!(merged["a"], Sum(22));
+    }
+}
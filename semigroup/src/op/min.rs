@@ -17,8 +17,8 @@ use crate::{Annotated, AnnotatedSemigroup};
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[construction(annotated, monoid, commutative, unit = Self(T::max_value()), unit_where = "T: num::Bounded")]
-#[properties_priv(annotated, monoid, commutative)]
+#[construction(annotated, monoid, commutative, idempotent, unit = Self(T::max_value()), unit_where = "T: num::Bounded")]
+#[properties_priv(selective)]
 pub struct Min<T: Ord>(pub T);
 impl<A, T: Ord> AnnotatedSemigroup<A> for Min<T> {
     fn annotated_op(base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A> {
@@ -28,7 +28,10 @@ impl<A, T: Ord> AnnotatedSemigroup<A> for Min<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_commutative, assert_monoid, assert_semigroup, Construction, Semigroup};
+    use crate::{
+        assert_commutative, assert_idempotent, assert_monoid, assert_semigroup, Construction,
+        Semigroup,
+    };
 
     use super::*;
 
@@ -50,6 +53,12 @@ mod tests {
         assert_commutative!(a, b, c);
     }
 
+    #[test]
+    fn test_min_idempotent() {
+        let (a, b, c) = (Min(1), Min(2), Min(3));
+        assert_idempotent!(a, b, c);
+    }
+
     #[test]
     fn test_min() {
         let (a, b) = (Min(1), Min(2));
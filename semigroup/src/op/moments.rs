@@ -0,0 +1,249 @@
+use num::Float;
+use semigroup_derive::properties_priv;
+
+use crate::{Commutative, Monoid, Semigroup};
+
+/// A semigroup construction that merges statistical moments of samples.
+/// - count
+/// - mean
+/// - variance, standard deviation
+/// - skewness, kurtosis
+///
+/// Moments are merged exactly by the parallel algorithm of Chan et al.
+/// with central moments up to the 4th, so it is numerically stable for `f32` and `f64`.
+/// # Properties
+/// <!-- properties -->
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::Moments, Semigroup};
+///
+/// let a: Moments<f64> = [2.0, 4.0, 4.0, 4.0].into_iter().collect();
+/// let b: Moments<f64> = [5.0, 5.0, 7.0, 9.0].into_iter().collect();
+///
+/// let m = a.semigroup(b);
+/// assert_eq!(m.count(), 8);
+/// assert_eq!(m.mean(), 5.0);
+/// assert_eq!(m.variance(), 4.0);
+/// assert_eq!(m.stddev(), 2.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[properties_priv(monoid, commutative, unit = Self::default())]
+pub struct Moments<F: Float> {
+    count: u64,
+    mean: F,
+    m2: F,
+    m3: F,
+    m4: F,
+}
+impl<F: Float> Semigroup for Moments<F> {
+    fn op(base: Self, other: Self) -> Self {
+        if base.count == 0 {
+            return other;
+        } else if other.count == 0 {
+            return base;
+        }
+        let (na, nb) = (cast::<F>(base.count), cast::<F>(other.count));
+        let count = base.count + other.count;
+        let n = cast::<F>(count);
+        let (three, four, six) = (cast::<F>(3), cast::<F>(4), cast::<F>(6));
+
+        let delta = other.mean - base.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * na * nb;
+
+        let mean = base.mean + delta_n * nb;
+        let m2 = base.m2 + other.m2 + term;
+        let m3 = base.m3
+            + other.m3
+            + term * delta_n * (na - nb)
+            + three * delta_n * (na * other.m2 - nb * base.m2);
+        let m4 = base.m4
+            + other.m4
+            + term * delta_n2 * (na * na - na * nb + nb * nb)
+            + six * delta_n2 * (na * na * other.m2 + nb * nb * base.m2)
+            + four * delta_n * (na * other.m3 - nb * base.m3);
+        Self {
+            count,
+            mean,
+            m2,
+            m3,
+            m4,
+        }
+    }
+}
+impl<F: Float> Commutative for Moments<F> {}
+impl<F: Float> Monoid for Moments<F> {
+    fn unit() -> Self {
+        Self {
+            count: 0,
+            mean: F::zero(),
+            m2: F::zero(),
+            m3: F::zero(),
+            m4: F::zero(),
+        }
+    }
+}
+impl<F: Float> From<F> for Moments<F> {
+    fn from(value: F) -> Self {
+        Self {
+            count: 1,
+            mean: value,
+            ..Self::unit()
+        }
+    }
+}
+impl<F: Float> FromIterator<F> for Moments<F> {
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        iter.into_iter()
+            .map(Self::from)
+            .fold(Self::unit(), Self::op)
+    }
+}
+impl<F: Float> Moments<F> {
+    /// The number of samples.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// The mean of samples, `0` if there are no samples.
+    pub fn mean(&self) -> F {
+        self.mean
+    }
+    /// The population variance of samples, `NaN` if there are no samples.
+    pub fn variance(&self) -> F {
+        self.m2 / cast(self.count)
+    }
+    /// The unbiased sample variance of samples, `NaN` if there are less than 2 samples.
+    pub fn sample_variance(&self) -> F {
+        self.m2 / cast(self.count.saturating_sub(1))
+    }
+    /// The population standard deviation of samples, `NaN` if there are no samples.
+    pub fn stddev(&self) -> F {
+        self.variance().sqrt()
+    }
+    /// The population skewness of samples, `NaN` if the variance is `0`.
+    pub fn skewness(&self) -> F {
+        cast::<F>(self.count).sqrt() * self.m3 / self.m2.powf(cast::<F>(3) / cast(2))
+    }
+    /// The population excess kurtosis of samples, `NaN` if the variance is `0`.
+    pub fn kurtosis(&self) -> F {
+        cast::<F>(self.count) * self.m4 / (self.m2 * self.m2) - cast(3)
+    }
+}
+
+fn cast<F: Float>(n: u64) -> F {
+    F::from(n).unwrap_or_else(|| unreachable!())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_commutative, assert_monoid, assert_semigroup, Semigroup};
+
+    use super::*;
+
+    fn assert_approx_eq(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "actual: {actual}, expected: {expected}"
+        );
+    }
+
+    /// Compare moments within relative error, because floating point operations are associative only approximately.
+    #[derive(Debug, Clone)]
+    struct Approx<F: Float>(Moments<F>);
+    impl<F: Float> PartialEq for Approx<F> {
+        fn eq(&self, other: &Self) -> bool {
+            let (Self(a), Self(b)) = (self, other);
+            let close = |x: F, y: F| {
+                (x - y).abs() <= F::epsilon().sqrt() * F::one().max(x.abs()).max(y.abs())
+            };
+            a.count == b.count
+                && close(a.mean, b.mean)
+                && close(a.m2, b.m2)
+                && close(a.m3, b.m3)
+                && close(a.m4, b.m4)
+        }
+    }
+    impl<F: Float> Semigroup for Approx<F> {
+        fn op(base: Self, other: Self) -> Self {
+            Self(Semigroup::op(base.0, other.0))
+        }
+    }
+    impl<F: Float> Commutative for Approx<F> {}
+    impl<F: Float> Monoid for Approx<F> {
+        fn unit() -> Self {
+            Self(Moments::unit())
+        }
+    }
+
+    fn approx_moments<F: Float>() -> [Approx<F>; 4] {
+        let moments = |data: &[f64]| Approx(data.iter().map(|&x| cast_f64::<F>(x)).collect());
+        [
+            moments(&[0.0, 2.0]),
+            moments(&[1.0]),
+            moments(&[-1.0, 3.0, 1.0, 0.1]),
+            moments(&[10.5, -7.25, 3.0]),
+        ]
+    }
+    fn cast_f64<F: Float>(x: f64) -> F {
+        F::from(x).unwrap_or_else(|| unreachable!())
+    }
+
+    #[test]
+    fn test_moments_as_semigroup() {
+        assert_semigroup!(&approx_moments::<f64>(), exhaustive);
+    }
+
+    #[test]
+    fn test_moments_as_monoid() {
+        assert_monoid!(&approx_moments::<f64>(), exhaustive);
+    }
+
+    #[test]
+    fn test_moments_commutative() {
+        assert_commutative!(&approx_moments::<f32>(), exhaustive);
+    }
+
+    #[test]
+    fn test_moments() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0, 10.0, 30.0];
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let central = |k| data.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / n;
+        let (variance, m3, m4) = (central(2), central(3), central(4));
+
+        let (left, right) = data.split_at(3);
+        let a: Moments<f64> = left.iter().copied().collect();
+        let b: Moments<f64> = right.iter().copied().collect();
+        for m in [a.semigroup(b), b.semigroup(a)] {
+            assert_eq!(m.count(), 10);
+            assert_approx_eq(m.mean(), mean);
+            assert_approx_eq(m.variance(), variance);
+            assert_approx_eq(m.sample_variance(), variance * n / (n - 1.0));
+            assert_approx_eq(m.stddev(), variance.sqrt());
+            assert_approx_eq(m.skewness(), m3 / variance.powf(1.5));
+            assert_approx_eq(m.kurtosis(), m4 / (variance * variance) - 3.0);
+        }
+    }
+
+    #[test]
+    fn test_moments_numerical_stability() {
+        let offset = 1e9;
+        let a: Moments<f64> = [4.0, 7.0, 13.0].map(|x| x + offset).into_iter().collect();
+        let b: Moments<f64> = [16.0].map(|x| x + offset).into_iter().collect();
+        let m = a.semigroup(b);
+        assert_approx_eq(m.mean(), offset + 10.0);
+        assert_approx_eq(m.sample_variance(), 30.0);
+    }
+
+    #[test]
+    fn test_moments_empty() {
+        let m = Moments::<f64>::unit();
+        assert_eq!(m.count(), 0);
+        assert_eq!(m.mean(), 0.0);
+        assert!(m.variance().is_nan());
+        assert_eq!(m.semigroup(Moments::from(1.5)), Moments::from(1.5));
+    }
+}
@@ -1,6 +1,6 @@
 use semigroup_derive::{properties_priv, ConstructionPriv};
 
-use crate::{Annotated, AnnotatedSemigroup};
+use crate::{Annotated, AnnotatedSemigroup, IntoOwned};
 
 /// A semigroup construction that returns the second value.
 /// # Properties
@@ -17,18 +17,24 @@ use crate::{Annotated, AnnotatedSemigroup};
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[construction(annotated)]
-#[properties_priv(annotated)]
+#[construction(annotated, idempotent)]
+#[properties_priv(selective)]
 pub struct Overwrite<T>(pub T);
 impl<T, A> AnnotatedSemigroup<A> for Overwrite<T> {
     fn annotated_op(_base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A> {
         other
     }
 }
+impl<T: IntoOwned> IntoOwned for Overwrite<T> {
+    type Owned = Overwrite<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Overwrite(self.0.into_owned())
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_semigroup, Construction, Semigroup};
+    use crate::{assert_idempotent, assert_semigroup, Construction, Semigroup};
 
     use super::*;
 
@@ -38,6 +44,12 @@ mod tests {
         assert_semigroup!(a, b, c);
     }
 
+    #[test]
+    fn test_overwrite_idempotent() {
+        let (a, b, c) = (Overwrite(1), Overwrite(2), Overwrite(3));
+        assert_idempotent!(a, b, c);
+    }
+
     #[test]
     fn test_overwrite() {
         let (a, b) = (Overwrite(Some(1)), Overwrite(Some(2)));
@@ -1,4 +1,6 @@
 use std::ops::Add;
+#[cfg(feature = "monoid")]
+use std::ops::Neg;
 
 use semigroup_derive::{properties_priv, ConstructionPriv};
 
@@ -8,6 +10,8 @@ use crate::Semigroup;
 /// # Properties
 /// <!-- properties -->
 ///
+/// [`Sum`] is also [`crate::Group`] if `T: Neg`, such as signed integers and floats.
+///
 /// # Examples
 /// ```
 /// use semigroup::{op::Sum, Construction, Semigroup};
@@ -20,17 +24,25 @@ use crate::Semigroup;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[construction(monoid, commutative, unit = Self(T::zero()), unit_where = "T: num::Zero")]
-#[properties_priv(monoid, commutative)]
+#[properties_priv]
 pub struct Sum<T: Add<Output = T>>(pub T);
 impl<T: Add<Output = T>> Semigroup for Sum<T> {
     fn op(base: Self, other: Self) -> Self {
         Self(base.0 + other.0)
     }
 }
+#[cfg(feature = "monoid")]
+impl<T: Add<Output = T> + Neg<Output = T> + num::Zero> crate::Group for Sum<T> {
+    fn inverse(self) -> Self {
+        Self(-self.0)
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_commutative, assert_monoid, assert_semigroup, Construction, Semigroup};
+    use crate::{
+        assert_commutative, assert_group, assert_monoid, assert_semigroup, Construction, Semigroup,
+    };
 
     use super::*;
 
@@ -46,6 +58,12 @@ mod tests {
         assert_monoid!(a, b, c);
     }
 
+    #[test]
+    fn test_sum_as_group() {
+        let (a, b, c) = (Sum(1), Sum(-2), Sum(3));
+        assert_group!(a, b, c);
+    }
+
     #[test]
     fn test_sum_commutative() {
         let (a, b, c) = (Sum(1), Sum(2), Sum(3));
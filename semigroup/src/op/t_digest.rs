@@ -0,0 +1,293 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use semigroup_derive::properties_priv;
+
+use crate::{Commutative, Semigroup};
+
+/// A semigroup construction merging two t-digests, a quantile sketch of floating-point values.
+/// - count, min, max, mean
+/// - quantile
+///
+/// Unlike [`crate::op::HdrHistogram`], negative and fractional values are also supported.
+/// `COMPRESSION` bounds the number of centroids to about `COMPRESSION`,
+/// and the quantile error is smaller near the tails. `NaN` and infinite values are ignored.
+///
+/// Merging t-digests is associative only approximately, quantiles of t-digests merged in different groupings may differ slightly.
+/// # Properties
+/// <!-- properties -->
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::TDigest, Semigroup};
+///
+/// let a: TDigest = (0..500).map(|x| -f64::from(x) / 10.0).collect();
+/// let b: TDigest = (0..500).map(|x| f64::from(x) / 10.0).collect();
+///
+/// let digest = a.semigroup(b);
+/// assert_eq!(digest.count(), 1000);
+/// assert_eq!(digest.min(), Some(-49.9));
+/// assert_eq!(digest.max(), Some(49.9));
+/// assert!((digest.quantile(0.9).unwrap() - 40.0).abs() < 0.5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[properties_priv(monoid, commutative, unit = Self::default())]
+pub struct TDigest<const COMPRESSION: usize = 100> {
+    centroids: Vec<Centroid>,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+impl<const COMPRESSION: usize> Semigroup for TDigest<COMPRESSION> {
+    fn op(base: Self, other: Self) -> Self {
+        if base.count == 0 {
+            return other;
+        } else if other.count == 0 {
+            return base;
+        }
+        let mut centroids = base.centroids;
+        centroids.extend(other.centroids);
+        Self {
+            centroids: Self::compress(centroids),
+            count: base.count + other.count,
+            min: base.min.min(other.min),
+            max: base.max.max(other.max),
+        }
+    }
+}
+impl<const COMPRESSION: usize> Commutative for TDigest<COMPRESSION> {}
+#[cfg(feature = "monoid")]
+impl<const COMPRESSION: usize> crate::Monoid for TDigest<COMPRESSION> {
+    fn unit() -> Self {
+        Self::default()
+    }
+}
+impl<const COMPRESSION: usize> From<f64> for TDigest<COMPRESSION> {
+    fn from(value: f64) -> Self {
+        Self::from_iter([value])
+    }
+}
+impl<const COMPRESSION: usize> FromIterator<f64> for TDigest<COMPRESSION> {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut digest = Self::default();
+        for mean in iter.into_iter().filter(|v| v.is_finite()) {
+            digest.centroids.push(Centroid { mean, weight: 1 });
+            if digest.centroids.len() > Self::BUFFER_CAPACITY {
+                digest.centroids = Self::compress(digest.centroids);
+            }
+            (digest.min, digest.max) = if digest.count == 0 {
+                (mean, mean)
+            } else {
+                (digest.min.min(mean), digest.max.max(mean))
+            };
+            digest.count += 1;
+        }
+        digest.centroids = Self::compress(digest.centroids);
+        digest
+    }
+}
+impl<const COMPRESSION: usize> TDigest<COMPRESSION> {
+    /// Values are buffered as centroids of weight `1` until the buffer exceeds this capacity,
+    /// so collecting values needs memory bounded by `COMPRESSION`.
+    const BUFFER_CAPACITY: usize = 5 * COMPRESSION;
+
+    /// The number of values.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// The minimum value, `None` if there are no values.
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+    /// The maximum value, `None` if there are no values.
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+    /// The mean of values, `None` if there are no values.
+    pub fn mean(&self) -> Option<f64> {
+        let sum: f64 = self
+            .centroids
+            .iter()
+            .map(|c| c.mean * c.weight as f64)
+            .sum();
+        (self.count > 0).then(|| sum / self.count as f64)
+    }
+    /// The estimated value at the quantile `q` in `[0, 1]`, `None` if there are no values.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let (total, rank) = (self.count as f64, q.clamp(0.0, 1.0) * self.count as f64);
+        let (mut prev, mut cumulative) = ((self.min, 0.0), 0.0);
+        for c in &self.centroids {
+            let center = cumulative + c.weight as f64 / 2.0;
+            if rank < center {
+                let (value, position) = prev;
+                return Some(value + (c.mean - value) * (rank - position) / (center - position));
+            }
+            prev = (c.mean, center);
+            cumulative += c.weight as f64;
+        }
+        let (value, position) = prev;
+        Some(value + (self.max - value) * (rank - position) / (total - position))
+    }
+
+    fn compress(mut centroids: Vec<Centroid>) -> Vec<Centroid> {
+        centroids.sort_by(|a, b| a.mean.total_cmp(&b.mean).then(a.weight.cmp(&b.weight)));
+        let total = centroids.iter().map(|c| c.weight).sum::<u64>() as f64;
+
+        let mut compressed = Vec::new();
+        let mut centroids = centroids.into_iter();
+        let Some(mut current) = centroids.next() else {
+            return compressed;
+        };
+        let (mut cumulative, mut limit) = (0.0, total * Self::k_to_q(Self::q_to_k(0.0) + 1.0));
+        for c in centroids {
+            if cumulative + (current.weight + c.weight) as f64 <= limit {
+                current.merge(c);
+            } else {
+                cumulative += current.weight as f64;
+                limit = total * Self::k_to_q(Self::q_to_k(cumulative / total) + 1.0);
+                compressed.push(std::mem::replace(&mut current, c));
+            }
+        }
+        compressed.push(current);
+        compressed
+    }
+    /// Scale function `k1`, which makes centroids near the tails small.
+    fn q_to_k(q: f64) -> f64 {
+        COMPRESSION as f64 / (2.0 * PI) * (2.0 * q - 1.0).asin()
+    }
+    fn k_to_q(k: f64) -> f64 {
+        let x = (k * 2.0 * PI / COMPRESSION as f64).clamp(-FRAC_PI_2, FRAC_PI_2);
+        (x.sin() + 1.0) / 2.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Centroid {
+    mean: f64,
+    weight: u64,
+}
+impl Centroid {
+    fn merge(&mut self, other: Self) {
+        self.weight += other.weight;
+        self.mean += (other.mean - self.mean) * other.weight as f64 / self.weight as f64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_commutative, assert_monoid, assert_semigroup, Monoid, Semigroup};
+
+    use super::*;
+
+    /// Compare t-digests by their quantiles within 5% of rank, because merging t-digests is associative only approximately.
+    #[derive(Debug, Clone)]
+    struct Approx(TDigest);
+    impl PartialEq for Approx {
+        fn eq(&self, other: &Self) -> bool {
+            let (Self(a), Self(b)) = (self, other);
+            a.count == b.count
+                && a.min() == b.min()
+                && a.max() == b.max()
+                && (0..=100).map(|q| q as f64 / 100.0).all(|q| {
+                    let (lower, upper) = (b.quantile(q - 0.05), b.quantile(q + 0.05));
+                    (lower..=upper).contains(&a.quantile(q))
+                })
+        }
+    }
+    impl Semigroup for Approx {
+        fn op(base: Self, other: Self) -> Self {
+            Self(Semigroup::op(base.0, other.0))
+        }
+    }
+    impl Commutative for Approx {}
+    impl Monoid for Approx {
+        fn unit() -> Self {
+            Self(TDigest::unit())
+        }
+    }
+
+    fn approx_digests() -> [Approx; 4] {
+        [
+            Approx((0..1000).map(|x| f64::from(x) / 10.0).collect()),
+            Approx((0..2000).map(|x| f64::from(x % 97) - 50.0).collect()),
+            Approx((0..1500).map(|x| f64::from(x).sqrt()).collect()),
+            Approx(TDigest::from(3.5)),
+        ]
+    }
+
+    #[test]
+    fn test_t_digest_as_semigroup() {
+        assert_semigroup!(&approx_digests(), exhaustive);
+    }
+
+    #[test]
+    fn test_t_digest_as_monoid() {
+        assert_monoid!(&approx_digests(), exhaustive);
+    }
+
+    #[test]
+    fn test_t_digest_commutative() {
+        assert_commutative!(&approx_digests(), exhaustive);
+    }
+
+    #[test]
+    fn test_t_digest() {
+        let a: TDigest = (1..=5000).map(f64::from).collect();
+        let b: TDigest = (5001..=10000).map(f64::from).collect();
+
+        let digest = a.semigroup(b);
+        assert_eq!(digest.count(), 10000);
+        assert_eq!(digest.min(), Some(1.0));
+        assert_eq!(digest.max(), Some(10000.0));
+        assert!((digest.mean().unwrap() - 5000.5).abs() < 1e-6);
+        assert_eq!(digest.quantile(0.0), Some(1.0));
+        assert_eq!(digest.quantile(1.0), Some(10000.0));
+        for q in [0.01, 0.1, 0.5, 0.9, 0.99] {
+            let estimated = digest.quantile(q).unwrap();
+            assert!(
+                (estimated - q * 10000.0).abs() < 10000.0 * 0.005,
+                "{q}: {estimated}"
+            );
+        }
+        assert!(digest.centroids.len() <= 100);
+    }
+
+    #[test]
+    fn test_t_digest_negative_and_nan() {
+        let digest: TDigest<50> = [-3.0, f64::NAN, -1.5, 2.0].into_iter().collect();
+        assert_eq!(digest.count(), 3);
+        assert_eq!(digest.min(), Some(-3.0));
+        assert_eq!(digest.quantile(0.5), Some(-1.5));
+        assert_eq!(TDigest::<50>::from(f64::NAN), TDigest::unit());
+        assert_eq!(TDigest::<50>::unit().quantile(0.5), None);
+    }
+
+    #[test]
+    fn test_t_digest_infinite() {
+        let values = [f64::INFINITY, f64::NEG_INFINITY, 1.0]
+            .into_iter()
+            .chain((0..600).map(f64::from))
+            .chain([f64::INFINITY; 5]);
+        let digest: TDigest = values.collect();
+        assert_eq!(digest.count(), 601);
+        assert_eq!(digest.min(), Some(0.0));
+        assert_eq!(digest.max(), Some(599.0));
+        assert!(digest.mean().unwrap().is_finite());
+        assert!(digest.quantile(0.99).unwrap().is_finite());
+        assert_eq!(TDigest::<50>::from(f64::INFINITY), TDigest::unit());
+    }
+
+    #[test]
+    fn test_t_digest_from_iter_bounded() {
+        let digest: TDigest = (0..100_000).map(f64::from).collect();
+        assert_eq!(digest.count(), 100_000);
+        assert_eq!(digest.min(), Some(0.0));
+        assert_eq!(digest.max(), Some(99_999.0));
+        assert!(digest.centroids.len() <= 100);
+        assert!((digest.quantile(0.5).unwrap() - 50_000.0).abs() < 100_000.0 * 0.005);
+    }
+}
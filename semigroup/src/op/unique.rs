@@ -0,0 +1,110 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display},
+    ops::{Deref, DerefMut},
+};
+
+use crate::{TryConstruction, TrySemigroup};
+
+/// A fallible construction that returns the non-`None` value, and fails if both values are `Some` and differ.
+///
+/// It is useful for fields that must not be silently overwritten, such as database URLs set by multiple sources.
+/// When it succeeds, the operation is *associative*, *commutative* and *idempotent*.
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::Unique, TrySemigroup};
+///
+/// let (a, b, c) = (Unique(None), Unique(Some(2)), Unique(Some(3)));
+///
+/// assert_eq!(a.try_semigroup(b).unwrap().into_inner(), Some(2));
+/// assert_eq!(b.try_semigroup(b).unwrap().into_inner(), Some(2));
+/// assert_eq!(b.try_semigroup(c).unwrap_err().to_string(), "conflicting values 2 and 3");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unique<T>(pub Option<T>);
+impl<T> Unique<T> {
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+impl<T> From<Option<T>> for Unique<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+impl<T> Deref for Unique<T> {
+    type Target = Option<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<T> DerefMut for Unique<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<T: PartialEq> TrySemigroup for Unique<T> {
+    type Error = UniqueError<T>;
+    fn try_op(base: Self, other: Self) -> Result<Self, Self::Error> {
+        match (base.0, other.0) {
+            (Some(b), Some(o)) if b != o => Err(UniqueError { base: b, other: o }),
+            (Some(b), _) => Ok(Self(Some(b))),
+            (None, o) => Ok(Self(o)),
+        }
+    }
+}
+impl<T: PartialEq> TryConstruction<Option<T>> for Unique<T> {
+    fn try_lift_op(base: Option<T>, other: Option<T>) -> Result<Option<T>, Self::Error> {
+        Self::try_op(Self(base), Self(other)).map(Self::into_inner)
+    }
+}
+
+/// The error of [`Unique`] that both values are `Some` and differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniqueError<T> {
+    pub base: T,
+    pub other: T,
+}
+impl<T: Debug> Display for UniqueError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conflicting values {:?} and {:?}", self.base, self.other)
+    }
+}
+impl<T: Debug> Error for UniqueError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_associative() {
+        let v = [Unique(None), Unique(Some(1)), Unique(Some(2))];
+        // errors may report different pairs, but succeed or fail together
+        for a in v {
+            for b in v {
+                for c in v {
+                    let ab_c = a.try_semigroup(b).and_then(|ab| ab.try_semigroup(c));
+                    let a_bc = b.try_semigroup(c).and_then(|bc| a.try_semigroup(bc));
+                    assert_eq!(ab_c.ok(), a_bc.ok());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unique() {
+        let (a, b) = (Unique(None), Unique(Some("value")));
+        assert_eq!(a.try_semigroup(b), Ok(Unique(Some("value"))));
+        assert_eq!(b.try_semigroup(a), Ok(Unique(Some("value"))));
+        assert_eq!(b.try_semigroup(b), Ok(Unique(Some("value"))));
+
+        let (a, b) = (Unique(Some(1)), Unique(Some(2)));
+        assert_eq!(a.try_semigroup(b), Err(UniqueError { base: 1, other: 2 }));
+        assert_eq!(
+            <Unique<_> as TryConstruction<_>>::try_lift_op(Some(2), Some(1)),
+            Err(UniqueError { base: 2, other: 1 })
+        );
+    }
+}
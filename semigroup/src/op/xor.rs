@@ -8,6 +8,8 @@ use crate::Semigroup;
 /// # Properties
 /// <!-- properties -->
 ///
+/// [`Xor`] is also [`crate::Group`], every value is its own inverse.
+///
 /// # Examples
 /// ```
 /// use semigroup::{op::Xor, Construction, Semigroup};
@@ -19,17 +21,25 @@ use crate::Semigroup;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash, ConstructionPriv)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[construction(monoid, commutative, unit = Self(T::zero()), unit_where = "T: num::Zero")]
-#[properties_priv(monoid, commutative)]
+#[properties_priv]
 pub struct Xor<T: BitXor<Output = T>>(pub T);
 impl<T: BitXor<Output = T>> Semigroup for Xor<T> {
     fn op(base: Self, other: Self) -> Self {
         Self(base.0 ^ other.0)
     }
 }
+#[cfg(feature = "monoid")]
+impl<T: BitXor<Output = T> + num::Zero> crate::Group for Xor<T> {
+    fn inverse(self) -> Self {
+        self
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::{assert_commutative, assert_monoid, assert_semigroup, Construction, Semigroup};
+    use crate::{
+        assert_commutative, assert_group, assert_monoid, assert_semigroup, Construction, Semigroup,
+    };
 
     use super::*;
 
@@ -45,6 +55,12 @@ mod tests {
         assert_monoid!(a, b, c);
     }
 
+    #[test]
+    fn test_xor_as_group() {
+        let (a, b, c) = (Xor(0b111), Xor(0b101), Xor(0b100));
+        assert_group!(a, b, c);
+    }
+
     #[test]
     fn test_xor_commutative() {
         let (a, b, c) = (Xor(0b111), Xor(0b101), Xor(0b100));
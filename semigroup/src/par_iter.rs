@@ -0,0 +1,121 @@
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+use crate::{Commutative, Semigroup};
+
+/// [`ParallelSemigroupIterator`] reduces an ordered parallel iterator by [`Semigroup`] operation.
+/// By *associativity*, the reduction can be tree-shaped, and the result is identical to the sequential fold.
+///
+/// # Examples
+/// ```
+/// use rayon::prelude::*;
+/// use semigroup::{op::Overwrite, ParallelSemigroupIterator, Semigroup};
+///
+/// let data: Vec<_> = (0..10000).map(Overwrite).collect();
+/// let reduced = data.clone().into_par_iter().par_semigroup_reduce();
+/// assert_eq!(reduced, data.into_iter().reduce(Semigroup::op));
+/// assert_eq!(reduced, Some(Overwrite(9999)));
+/// ```
+pub trait ParallelSemigroupIterator: IndexedParallelIterator {
+    /// Reduce items in order, return [`None`] if the iterator is empty.
+    fn par_semigroup_reduce(self) -> Option<Self::Item>
+    where
+        Self::Item: Semigroup,
+    {
+        self.reduce_with(Semigroup::op)
+    }
+
+    /// Fold items in order, starting from [`crate::Monoid::unit`] in each split.
+    ///
+    /// # Examples
+    /// ```
+    /// use rayon::prelude::*;
+    /// use semigroup::{op::Coalesce, ParallelSemigroupIterator};
+    ///
+    /// let data = (0..10000).map(|i| Coalesce((i % 7 == 6).then_some(i)));
+    /// assert_eq!(data.collect::<Vec<_>>().into_par_iter().par_monoid_fold(), Coalesce(Some(6)));
+    /// ```
+    #[cfg(feature = "monoid")]
+    fn par_monoid_fold(self) -> Self::Item
+    where
+        Self::Item: crate::Monoid,
+    {
+        self.reduce(crate::Monoid::unit, Semigroup::op)
+    }
+}
+impl<I: IndexedParallelIterator> ParallelSemigroupIterator for I {}
+
+/// [`ParallelCommutativeIterator`] reduces any parallel iterator by [`Commutative`] operation.
+/// By *commutativity*, the order of items does not matter, so even unordered iterators such as
+/// [`rayon::iter::ParallelBridge`] give the result identical to the sequential fold.
+///
+/// # Examples
+/// ```
+/// use rayon::prelude::*;
+/// use semigroup::{op::Sum, ParallelCommutativeIterator};
+///
+/// let reduced = (1..=10000).map(Sum).par_bridge().par_commutative_reduce();
+/// assert_eq!(reduced, Some(Sum(50005000)));
+/// ```
+pub trait ParallelCommutativeIterator: ParallelIterator {
+    /// Reduce items in any order, return [`None`] if the iterator is empty.
+    fn par_commutative_reduce(self) -> Option<Self::Item>
+    where
+        Self::Item: Commutative,
+    {
+        self.reduce_with(Semigroup::op)
+    }
+
+    /// Fold items in any order, starting from [`crate::Monoid::unit`] in each split.
+    #[cfg(feature = "monoid")]
+    fn par_commutative_fold(self) -> Self::Item
+    where
+        Self::Item: Commutative + crate::Monoid,
+    {
+        self.reduce(crate::Monoid::unit, Semigroup::op)
+    }
+}
+impl<I: ParallelIterator> ParallelCommutativeIterator for I {}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::{
+        op::{Concat, Max, Min, Overwrite, Sum},
+        Monoid, SemigroupIterator,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_par_semigroup_reduce_ordered() {
+        let data: Vec<_> = (0..100000).map(|i| Concat(vec![i])).collect();
+        let expected = data.clone().into_iter().reduce(Semigroup::op);
+        assert_eq!(
+            data.clone().into_par_iter().par_semigroup_reduce(),
+            expected
+        );
+        assert_eq!(
+            data.clone().into_par_iter().par_monoid_fold(),
+            data.into_iter().fold_final(Concat::unit())
+        );
+
+        let empty = Vec::<Overwrite<u32>>::new();
+        assert_eq!(empty.clone().into_par_iter().par_semigroup_reduce(), None);
+        assert_eq!(
+            Vec::<Sum<u32>>::new().into_par_iter().par_monoid_fold(),
+            Sum(0)
+        );
+    }
+
+    #[test]
+    fn test_par_commutative_reduce_unordered() {
+        let data = (1..=100000u64).map(|i| (Sum(i), Max(i), Min(i)));
+        let sum = data.clone().map(|t| t.0).par_bridge();
+        assert_eq!(sum.par_commutative_fold(), Sum(5000050000));
+        let max = data.clone().map(|t| t.1).par_bridge();
+        assert_eq!(max.par_commutative_reduce(), Some(Max(100000)));
+        let min = data.map(|t| t.2).par_bridge().filter(|m| m.0 > 10);
+        assert_eq!(min.par_commutative_reduce(), Some(Min(11)));
+    }
+}
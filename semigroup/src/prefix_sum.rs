@@ -0,0 +1,140 @@
+use std::ops::{Range, RangeBounds};
+
+use crate::{range::indices, Group, Monoid};
+
+/// [`PrefixSum`] is a data structure for range queries by prefix folds.
+/// It requires the underlying operation on the data to form a [`Monoid`], and [`Group`] for arbitrary range queries.
+///
+/// Unlike [`crate::segment_tree::SegmentTree`], the data cannot be updated except by [`PrefixSum::push`].
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::Sum, prefix_sum::PrefixSum};
+/// let data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+/// let mut prefix_sum: PrefixSum<_> = data.into_iter().map(Sum).collect();
+/// assert_eq!(prefix_sum.fold(3..=5).0, 12);
+/// prefix_sum.push(Sum(11));
+/// assert_eq!(prefix_sum.fold(9..).0, 30);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrefixSum<T> {
+    prefix: Vec<T>, // prefix[i] = fold(..i), so prefix[0] = unit()
+}
+impl<T: Monoid + Clone> FromIterator<T> for PrefixSum<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut prefix_sum = Self::new();
+        prefix_sum.extend(iter);
+        prefix_sum
+    }
+}
+impl<T: Monoid + Clone> From<Vec<T>> for PrefixSum<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self::from_iter(v)
+    }
+}
+impl<T: Monoid + Clone> Default for PrefixSum<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> PrefixSum<T> {
+    /// **O(1)**, return this prefix sum's number of data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+    /// **O(1)**, check if this prefix sum is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+impl<T: Monoid + Clone> PrefixSum<T> {
+    /// **O(1)**, init empty prefix sum.
+    pub fn new() -> Self {
+        let prefix = vec![T::unit()];
+        Self { prefix }
+    }
+    /// amortized **O(1)**, push `x` to the prefix sum.
+    pub fn push(&mut self, x: T) {
+        let last = self.prefix[self.len()].clone();
+        self.prefix.push(T::op(last, x));
+    }
+    /// **O(1)**, fold the prefix `..end`.
+    pub fn prefix_fold(&self, end: usize) -> T {
+        self.prefix[end.min(self.len())].clone()
+    }
+}
+impl<T: Group + Clone> PrefixSum<T> {
+    /// **O(1)**, fold the range by `op(inverse(fold(..start)), fold(..end))`.
+    pub fn fold<R>(&self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = indices(range, self.len());
+        if start >= end {
+            return T::unit();
+        }
+        T::op(
+            self.prefix[start].clone().inverse(),
+            self.prefix[end].clone(),
+        )
+    }
+}
+impl<T: Monoid + Clone> Extend<T> for PrefixSum<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iterator = iter.into_iter();
+        self.prefix.reserve(iterator.size_hint().0);
+        iterator.for_each(|x| self.push(x));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::op::{Sum, Xor};
+
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let data = [2, -5, 122, -33, -12, 14, -55, 500, 3];
+        let mut prefix_sum: PrefixSum<_> = data.into_iter().map(Sum).collect();
+        assert_eq!(prefix_sum.len(), 9);
+        for i in 0..=data.len() {
+            for j in i..=data.len() {
+                assert_eq!(prefix_sum.fold(i..j).0, data[i..j].iter().sum::<i32>());
+            }
+            assert_eq!(prefix_sum.prefix_fold(i).0, data[..i].iter().sum::<i32>());
+        }
+        prefix_sum.push(Sum(100));
+        assert_eq!(prefix_sum.fold(7..).0, 603);
+        assert_eq!(prefix_sum.prefix_fold(100).0, 636);
+    }
+
+    #[test]
+    fn test_xor() {
+        let data = [0b1010, 0b0110, 0b1111, 0b0001];
+        let prefix_xor: PrefixSum<_> = data.into_iter().map(Xor).collect();
+        assert_eq!(prefix_xor.fold(..).0, 0b0010);
+        assert_eq!(prefix_xor.fold(1..3).0, 0b1001);
+        assert_eq!(prefix_xor.fold(2..=2).0, 0b1111);
+    }
+
+    #[test]
+    fn test_empty_prefix_sum() {
+        let empty = PrefixSum::<Sum<i64>>::from(vec![]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.fold(..), Sum(0));
+        assert_eq!(empty.fold(0..0), Sum(0));
+        assert_eq!(empty.prefix_fold(0), Sum(0));
+    }
+
+    #[test]
+    fn test_descending_empty_range() {
+        let prefix_sum: PrefixSum<_> = (0..10).map(Sum).collect();
+        #[allow(clippy::reversed_empty_ranges)]
+        let range = 5..3;
+        assert_eq!(prefix_sum.fold(range), Sum(0));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{Annotated, AnnotatedSemigroup, ConstructionAnnotated};
+
+/// [`Provenance`] is an annotation that records every source contributing to a value, not only the winner.
+/// It is generated per field by `#[semigroup(annotated, provenance)]` of [`crate::Semigroup`] derive.
+///
+/// # Examples
+/// ```
+/// use semigroup::{Annotate, Semigroup};
+///
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(annotated, provenance, with = "semigroup::op::Coalesce")]
+/// struct Config<'a> {
+///     num: Option<u32>,
+///     str: Option<&'a str>,
+/// }
+///
+/// let file = Config { num: Some(1), str: Some("file") }.annotated("file");
+/// let env = Config { num: Some(2), str: None }.annotated("env");
+/// let cli = Config { num: Some(3), str: None }.annotated("cli");
+///
+/// let config = file.semigroup(env).semigroup(cli);
+/// assert_eq!(config.value(), &Config { num: Some(1), str: Some("file") });
+///
+/// let num = &config.annotation().num;
+/// assert_eq!(num.winner(), &"file");
+/// assert_eq!(num.sources(), &["file", "env", "cli"]);
+/// assert_eq!(num.shadowed().collect::<Vec<_>>(), vec![&"env", &"cli"]);
+///
+/// // `env` and `cli` offered no `str`, so they are not shadowed
+/// let str = &config.annotation().str;
+/// assert_eq!(str.sources(), &["file", "env", "cli"]);
+/// assert_eq!(str.shadowed().count(), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Provenance<A> {
+    sources: Vec<A>,
+    offered: Vec<bool>, // offered[i] = whether sources[i] offered a value
+    winner: usize,
+}
+impl<A> Provenance<A> {
+    /// Whether the source offered a value is settled by its first operation.
+    pub fn new(source: A) -> Self {
+        Self {
+            sources: vec![source],
+            offered: vec![true],
+            winner: 0,
+        }
+    }
+    /// The source whose value was chosen.
+    pub fn winner(&self) -> &A {
+        &self.sources[self.winner]
+    }
+    pub fn into_winner(mut self) -> A {
+        self.sources.swap_remove(self.winner)
+    }
+    /// All sources that took part in the operation, in operated order.
+    pub fn sources(&self) -> &[A] {
+        &self.sources
+    }
+    pub fn into_sources(self) -> Vec<A> {
+        self.sources
+    }
+    /// The sources whose values were overridden by the winner, in operated order.
+    /// Sources that offered no value, such as `Coalesce(None)`, are not shadowed.
+    pub fn shadowed(&self) -> impl Iterator<Item = &A> {
+        let winner = self.winner;
+        self.sources
+            .iter()
+            .zip(&self.offered)
+            .enumerate()
+            .filter_map(move |(i, (s, &offered))| (i != winner && offered).then_some(s))
+    }
+
+    /// Operate values by [`AnnotatedSemigroup`], and chain the provenance of `base` and `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use semigroup::{op::Coalesce, Annotated, Provenance};
+    ///
+    /// let a = Annotated::new(Coalesce(Some(1)), Provenance::new("first"));
+    /// let b = Annotated::new(Coalesce(None), Provenance::new("second"));
+    /// let c = Annotated::new(Coalesce(Some(3)), Provenance::new("third"));
+    /// let ab = Provenance::annotated_op(a, b);
+    /// let (value, provenance) = Provenance::annotated_op(ab, c).into_parts();
+    /// assert_eq!(value, Coalesce(Some(1)));
+    /// assert_eq!(provenance.winner(), &"first");
+    /// assert_eq!(provenance.sources(), &["first", "second", "third"]);
+    /// assert_eq!(provenance.shadowed().collect::<Vec<_>>(), vec![&"third"]);
+    /// ```
+    pub fn annotated_op<T: AnnotatedSemigroup<bool>>(
+        base: Annotated<T, Self>,
+        other: Annotated<T, Self>,
+    ) -> Annotated<T, Self> {
+        let (base_value, mut base_provenance) = base.into_parts();
+        let (other_value, mut other_provenance) = other.into_parts();
+        base_provenance.settle(&base_value);
+        other_provenance.settle(&other_value);
+        let (value, is_base) = T::annotated_op(
+            Annotated::new(base_value, true),
+            Annotated::new(other_value, false),
+        )
+        .into_parts();
+
+        let offset = base_provenance.sources.len();
+        if !is_base {
+            base_provenance.winner = offset + other_provenance.winner;
+        }
+        base_provenance.sources.extend(other_provenance.sources);
+        base_provenance.offered.extend(other_provenance.offered);
+        Annotated::new(value, base_provenance)
+    }
+    /// Operate values by [`ConstructionAnnotated`] `C` like [`Provenance::annotated_op`].
+    /// When `T` does not implement [`AnnotatedSemigroup`], this function can be used.
+    pub fn lift_annotated_op<T, C: ConstructionAnnotated<T, bool>>(
+        base: Annotated<T, Self>,
+        other: Annotated<T, Self>,
+    ) -> Annotated<T, Self> {
+        Self::annotated_op(base.map(C::from), other.map(C::from)).map(C::into_inner)
+    }
+
+    /// a single source has never been operated, so its value is the value of the source
+    fn settle<T: AnnotatedSemigroup<bool>>(&mut self, value: &T) {
+        if let [offered] = &mut self.offered[..] {
+            *offered = value.offers_value();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::op::{Coalesce, Overwrite};
+
+    use super::*;
+
+    #[test]
+    fn test_provenance_chain() {
+        let [a, b, c] = [(Some(1), "a"), (None, "b"), (Some(3), "c")]
+            .map(|(v, s)| Annotated::new(Coalesce(v), Provenance::new(s)));
+
+        let ab = Provenance::annotated_op(a.clone(), b.clone());
+        assert_eq!(ab.value(), &Coalesce(Some(1)));
+        assert_eq!(ab.annotation().winner(), &"a");
+        assert_eq!(ab.annotation().shadowed().count(), 0);
+
+        let bc = Provenance::annotated_op(b.clone(), c.clone());
+        assert_eq!(bc.annotation().winner(), &"c");
+        assert_eq!(bc.annotation().sources(), &["b", "c"]);
+        assert_eq!(bc.annotation().shadowed().count(), 0);
+
+        let ab_c = Provenance::annotated_op(ab, c.clone());
+        let a_bc = Provenance::annotated_op(a, bc);
+        assert_eq!(ab_c, a_bc);
+        assert_eq!(ab_c.annotation().winner(), &"a");
+        assert_eq!(ab_c.annotation().sources(), &["a", "b", "c"]);
+        assert_eq!(ab_c.annotation().shadowed().collect::<Vec<_>>(), vec![&"c"]);
+        assert_eq!(ab_c.into_annotation().into_winner(), "a");
+    }
+
+    #[test]
+    fn test_provenance_winner_in_other() {
+        let [a, b, c] = ["a", "b", "c"].map(|s| Annotated::new(Overwrite(s), Provenance::new(s)));
+        let bc = Provenance::annotated_op(b, c);
+        let abc = Provenance::annotated_op(a, bc);
+        assert_eq!(abc.value(), &Overwrite("c"));
+        assert_eq!(abc.annotation().winner(), &"c");
+        assert_eq!(
+            abc.annotation().shadowed().collect::<Vec<_>>(),
+            vec![&"a", &"b"]
+        );
+        assert_eq!(abc.into_annotation().into_sources(), vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,20 @@
+use std::ops::{Bound, Range, RangeBounds};
+
+/// **O(1)**, convert the range into the indices bounded by `len`.
+pub(crate) fn indices<R>(range: R, len: usize) -> Range<usize>
+where
+    R: RangeBounds<usize>,
+{
+    // TODO `std::slice::range` is nightly only https://doc.rust-lang.org/std/slice/fn.range.html
+    let start = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Excluded(&l) => l + 1,
+        Bound::Included(&l) => l,
+    };
+    let end = match range.end_bound() {
+        Bound::Unbounded => len,
+        Bound::Excluded(&r) => r.min(len),
+        Bound::Included(&r) => (r + 1).min(len),
+    };
+    start..end
+}
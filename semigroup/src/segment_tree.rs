@@ -1,13 +1,16 @@
-use std::ops::{Bound, Range, RangeBounds};
+use std::ops::{Range, RangeBounds};
 
-use crate::Monoid;
+use crate::{range::indices, Monoid};
 
 pub mod index;
 pub mod iter;
+pub mod lazy;
 
 /// [`SegmentTree`] is a data structure for efficient range queries based on perfect binary tree.
 /// It requires the underlying operation on the data to form a [`Monoid`].
 ///
+/// With the `serde` feature, it is serialized as its leaves, and reconstructed on deserialization.
+///
 /// # Examples
 /// ```
 /// use semigroup::{op::Sum, segment_tree::SegmentTree};
@@ -142,18 +145,7 @@ impl<T: Monoid + Clone> SegmentTree<T> {
     where
         R: RangeBounds<usize>,
     {
-        // TODO `std::slice::range` is nightly only https://doc.rust-lang.org/std/slice/fn.range.html
-        let start = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Excluded(&l) => (l + 1).max(0),
-            Bound::Included(&l) => l.max(0),
-        };
-        let end = match range.end_bound() {
-            Bound::Unbounded => self.len(),
-            Bound::Excluded(&r) => r.min(self.len()),
-            Bound::Included(&r) => (r + 1).min(self.len()),
-        };
-        start..end
+        indices(range, self.len())
     }
 
     /// **O(log(n))**, fold the range.
@@ -163,23 +155,109 @@ impl<T: Monoid + Clone> SegmentTree<T> {
     {
         let Range { start, end } = self.indices(range);
         let (mut left, mut right) = (self.leaf_offset() + start, self.leaf_offset() + end);
-        let mut res = T::unit();
+        let (mut left_res, mut right_res) = (T::unit(), T::unit());
         while left < right {
             if left % 2 == 1 {
-                res = T::semigroup(res, self.tree[left].clone());
+                left_res = T::semigroup(left_res, self.tree[left].clone());
                 left += 1;
             }
             if right % 2 == 1 {
                 right -= 1;
-                res = T::semigroup(self.tree[right].clone(), res);
+                right_res = T::semigroup(self.tree[right].clone(), right_res);
             }
             left /= 2;
             right /= 2;
         }
-        res
+        T::semigroup(left_res, right_res)
+    }
+
+    /// **O(log(n))**, search the maximum `end` such that `pred(fold(start..end))` is true.
+    /// `pred` must be true for [`Monoid::unit`], and monotone, that is, once it becomes false, it never turns true for longer ranges.
+    ///
+    /// # Panics
+    /// - If `start` is greater than [`Self::len`].
+    pub fn max_right<F>(&self, start: usize, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        assert!(
+            start <= self.len(),
+            "start {start} out of range for segment tree of length {}",
+            self.len()
+        );
+        if start == self.len() {
+            return self.len();
+        }
+        let leaf_offset = self.leaf_offset();
+        let (mut node, mut res) = (leaf_offset + start, T::unit());
+        loop {
+            while node % 2 == 0 {
+                node /= 2;
+            }
+            let next = T::semigroup(res.clone(), self.tree[node].clone());
+            if !pred(&next) {
+                while node < leaf_offset {
+                    node *= 2;
+                    let next = T::semigroup(res.clone(), self.tree[node].clone());
+                    if pred(&next) {
+                        res = next;
+                        node += 1;
+                    }
+                }
+                return node - leaf_offset;
+            }
+            res = next;
+            node += 1;
+            if node.is_power_of_two() {
+                return self.len();
+            }
+        }
+    }
+    /// **O(log(n))**, search the minimum `start` such that `pred(fold(start..end))` is true.
+    /// `pred` must be true for [`Monoid::unit`], and monotone, that is, once it becomes false, it never turns true for longer ranges.
+    ///
+    /// # Panics
+    /// - If `end` is greater than [`Self::len`].
+    pub fn min_left<F>(&self, end: usize, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        assert!(
+            end <= self.len(),
+            "end {end} out of range for segment tree of length {}",
+            self.len()
+        );
+        if end == 0 {
+            return 0;
+        }
+        let leaf_offset = self.leaf_offset();
+        let (mut node, mut res) = (leaf_offset + end, T::unit());
+        loop {
+            node -= 1;
+            while node > 1 && node % 2 == 1 {
+                node /= 2;
+            }
+            let next = T::semigroup(self.tree[node].clone(), res.clone());
+            if !pred(&next) {
+                while node < leaf_offset {
+                    node = node * 2 + 1;
+                    let next = T::semigroup(self.tree[node].clone(), res.clone());
+                    if pred(&next) {
+                        res = next;
+                        node -= 1;
+                    }
+                }
+                return node + 1 - leaf_offset;
+            }
+            res = next;
+            if node.is_power_of_two() {
+                return 0;
+            }
+        }
     }
 
     /// **O(log^2(n))**, search the leftmost leaf where `cmp(x)` is true in the range.
+    /// For a monotone predicate over prefixes, [`Self::max_right`] is faster.
     pub fn bisect_left<R, F>(&self, range: R, cmp: F) -> Option<usize>
     where
         R: RangeBounds<usize>,
@@ -197,6 +275,7 @@ impl<T: Monoid + Clone> SegmentTree<T> {
         cmp(&self.tree[self.leaf_offset() + start]).then_some(start)
     }
     /// **O(log^2(n))**, search the rightmost leaf where `cmp(x)` is true in the range.
+    /// For a monotone predicate over suffixes, [`Self::min_left`] is faster.
     pub fn bisect_right<R, F>(&self, range: R, cmp: F) -> Option<usize>
     where
         R: RangeBounds<usize>,
@@ -225,6 +304,21 @@ impl<T: Monoid + Clone> Extend<T> for SegmentTree<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for SegmentTree<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // serialize only leaves, internal nodes are derived data
+        serializer.collect_seq(&self[..])
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, T: Monoid + Clone + serde::Deserialize<'de>> serde::Deserialize<'de> for SegmentTree<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // reconstruct internal nodes from leaves, so that invalid tree cannot be deserialized
+        Vec::<T>::deserialize(deserializer).map(Self::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -378,6 +472,65 @@ mod tests {
         assert_eq!(max_tree.bisect_right(..5, |&Max(x)| x >= -5), Some(4));
     }
 
+    #[test]
+    fn test_max_right_min_left() {
+        let data = [-22, -5, 122, -33, -12, 14, -55, 500, 3];
+        let max_tree: SegmentTree<_> = data.into_iter().map(Max).collect();
+        assert_eq!(max_tree.max_right(0, |&Max(x)| x < 100), 2);
+        assert_eq!(max_tree.max_right(3, |&Max(x)| x < 100), 7);
+        assert_eq!(max_tree.max_right(8, |&Max(x)| x < 100), 9);
+        assert_eq!(max_tree.max_right(9, |&Max(x)| x < 100), 9);
+        assert_eq!(max_tree.max_right(2, |&Max(x)| x < 100), 2);
+        assert_eq!(max_tree.min_left(9, |&Max(x)| x < 100), 8);
+        assert_eq!(max_tree.min_left(7, |&Max(x)| x < 100), 3);
+        assert_eq!(max_tree.min_left(2, |&Max(x)| x < 100), 0);
+        assert_eq!(max_tree.min_left(0, |&Max(x)| x < 100), 0);
+        assert_eq!(max_tree.min_left(3, |&Max(x)| x < 100), 3);
+
+        let sum_tree: SegmentTree<_> = (0..100).map(|i| Sum((i * 37) % 11)).collect();
+        for bound in [0, 1, 10, 50, 200, 1000] {
+            for i in 0..=sum_tree.len() {
+                let expected = (i..=sum_tree.len())
+                    .take_while(|&j| sum_tree.fold(i..j).0 <= bound)
+                    .last()
+                    .unwrap();
+                assert_eq!(sum_tree.max_right(i, |&Sum(x)| x <= bound), expected);
+                let expected = (0..=i)
+                    .rev()
+                    .take_while(|&j| sum_tree.fold(j..i).0 <= bound)
+                    .last()
+                    .unwrap();
+                assert_eq!(sum_tree.min_left(i, |&Sum(x)| x <= bound), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "start 10 out of range for segment tree of length 9")]
+    fn test_max_right_out_of_range() {
+        let max_tree: SegmentTree<_> = (0..9).map(Max).collect();
+        max_tree.max_right(10, |_| true);
+    }
+
+    #[test]
+    fn test_fold_non_commutative() {
+        let data = ["a", "b", "c", "d", "e"];
+        let coalesce_tree: SegmentTree<_> = data
+            .into_iter()
+            .map(|s| OptionMonoid::from(Coalesce(Some(s))))
+            .collect();
+        for i in 0..data.len() {
+            for j in i + 1..=data.len() {
+                assert_eq!(
+                    coalesce_tree.fold(i..j),
+                    OptionMonoid::from(Coalesce(Some(data[i])))
+                );
+            }
+        }
+        assert_eq!(coalesce_tree.max_right(1, |x| x.0.is_none()), 1);
+        assert_eq!(coalesce_tree.min_left(4, |x| x.0.is_none()), 4);
+    }
+
     #[test]
     fn test_empty_tree() {
         let empty = SegmentTree::<OptionMonoid<Coalesce<u64>>>::from(vec![]);
@@ -387,6 +540,8 @@ mod tests {
         assert_eq!(empty.tree, vec![OptionMonoid::unit(), OptionMonoid::unit()]);
         assert_eq!(empty.fold(..), OptionMonoid::unit());
         assert_eq!(empty.fold(0..0), OptionMonoid::unit());
+        assert_eq!(empty.max_right(0, |_| true), 0);
+        assert_eq!(empty.min_left(0, |_| true), 0);
     }
 
     #[test]
@@ -544,4 +699,19 @@ mod tests {
         assert_eq!(sum_tree.fold(10..9).0, 0);
         assert_eq!(sum_tree.fold(9..8).0, 0);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let sum_tree: SegmentTree<_> = (0..10).map(Sum).collect();
+        let json = serde_json::to_string(&sum_tree).unwrap();
+        assert_eq!(json, "[0,1,2,3,4,5,6,7,8,9]");
+        let deserialized: SegmentTree<Sum<i32>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, sum_tree);
+        assert_eq!(deserialized.fold(3..=5).0, 12);
+
+        let empty: SegmentTree<Sum<i32>> = serde_json::from_str("[]").unwrap();
+        assert_eq!(empty, SegmentTree::from(vec![]));
+        assert!(serde_json::from_str::<SegmentTree<Sum<i32>>>(r#"{"tree":[0],"len":1}"#).is_err());
+    }
 }
@@ -0,0 +1,374 @@
+use std::ops::{Range, RangeBounds};
+
+use crate::{
+    segment_tree::{iter::IntoIter, SegmentTree},
+    Monoid, MonoidAction,
+};
+
+/// [`LazySegmentTree`] is a [`SegmentTree`] that supports range updates by lazy propagation.
+/// It requires the action on the data to form a [`MonoidAction`].
+///
+/// # Examples
+/// ## Range add
+/// ```
+/// use semigroup::{op::Sum, segment_tree::lazy::LazySegmentTree};
+/// let data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+/// let mut sum_tree: LazySegmentTree<_, Sum<i32>> = data.into_iter().map(Sum).collect();
+/// assert_eq!(sum_tree.fold(3..=5).0, 12);
+/// sum_tree.apply(4..8, Sum(10));
+/// assert_eq!(sum_tree.fold(3..=5).0, 32);
+/// ```
+///
+/// ## Range assign
+/// ```
+/// use semigroup::{op::{Overwrite, Min}, segment_tree::lazy::LazySegmentTree, OptionMonoid};
+/// let data = [2, -5, 122, -33, -12, 14, -55, 500, 3];
+/// let mut min_tree: LazySegmentTree<_, OptionMonoid<Overwrite<_>>> = data.into_iter().map(Min).collect();
+/// assert_eq!(min_tree.fold(..).0, -55);
+/// min_tree.apply(3..7, OptionMonoid::from(Overwrite(Min(0))));
+/// assert_eq!(min_tree.fold(..).0, -5);
+/// assert_eq!(min_tree.to_vec(), [Min(2), Min(-5), Min(122), Min(0), Min(0), Min(0), Min(0), Min(500), Min(3)]);
+/// ```
+///
+/// Equality compares the elements with the postponed actions applied, not the internal nodes.
+#[derive(Debug, Clone, Default)]
+pub struct LazySegmentTree<T, F> {
+    segment_tree: SegmentTree<T>,
+    lazy: Vec<F>, // pending actions of internal nodes, same indices as segment tree
+}
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> FromIterator<T> for LazySegmentTree<T, F> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(SegmentTree::from_iter(iter))
+    }
+}
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> From<Vec<T>> for LazySegmentTree<T, F> {
+    fn from(v: Vec<T>) -> Self {
+        Self::from(SegmentTree::from(v))
+    }
+}
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> From<SegmentTree<T>> for LazySegmentTree<T, F> {
+    fn from(segment_tree: SegmentTree<T>) -> Self {
+        let lazy = vec![F::unit(); segment_tree.leaf_offset()];
+        Self { segment_tree, lazy }
+    }
+}
+impl<T, F> PartialEq for LazySegmentTree<T, F>
+where
+    T: Monoid + Clone + PartialEq,
+    F: MonoidAction<T> + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.to_vec() == other.to_vec()
+    }
+}
+impl<T, F> Eq for LazySegmentTree<T, F>
+where
+    T: Monoid + Clone + Eq,
+    F: MonoidAction<T> + Clone,
+{
+}
+impl<T, F> LazySegmentTree<T, F> {
+    /// **O(1)**, init empty lazy segment tree.
+    pub fn new() -> Self {
+        let (segment_tree, lazy) = (SegmentTree::new(), Vec::new());
+        Self { segment_tree, lazy }
+    }
+    /// **O(1)**, return this lazy segment tree's number of data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segment_tree.len()
+    }
+    /// **O(1)**, check if this lazy segment tree is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segment_tree.is_empty()
+    }
+    /// **O(1)**, get height of the perfect binary tree.
+    #[inline]
+    fn height(&self) -> u32 {
+        self.segment_tree.leaf_offset().trailing_zeros()
+    }
+    /// **O(1)**, get number of leaves under the node.
+    #[inline]
+    fn node_size(&self, node: usize) -> usize {
+        self.segment_tree.leaf_offset() >> node.ilog2()
+    }
+}
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> LazySegmentTree<T, F> {
+    /// **O(1)**, apply `action` to the node, and postpone it for the children.
+    fn apply_node(&mut self, node: usize, action: &F) {
+        let size = self.node_size(node);
+        let tree = &mut self.segment_tree.tree;
+        tree[node] = action.act(tree[node].clone(), size);
+        if node < self.segment_tree.leaf_offset() {
+            self.lazy[node] = F::op(self.lazy[node].clone(), action.clone());
+        }
+    }
+    /// **O(1)**, propagate the postponed action of the node to the children.
+    fn push(&mut self, node: usize) {
+        let action = std::mem::replace(&mut self.lazy[node], F::unit());
+        self.apply_node(node * 2, &action);
+        self.apply_node(node * 2 + 1, &action);
+    }
+    /// **O(1)**, recalculate the node from the children.
+    fn pull(&mut self, node: usize) {
+        let tree = &mut self.segment_tree.tree;
+        tree[node] = T::op(tree[node * 2].clone(), tree[node * 2 + 1].clone());
+    }
+    /// **O(log(n))**, propagate the postponed actions of the ancestors of the leaf.
+    fn push_ancestors(&mut self, leaf: usize) {
+        for h in (1..=self.height()).rev() {
+            if (leaf >> h) << h != leaf {
+                self.push(leaf >> h);
+            }
+        }
+    }
+    /// **O(n)**, propagate all postponed actions to the leaves.
+    fn push_all(&mut self) {
+        for node in 1..self.segment_tree.leaf_offset() {
+            self.push(node);
+        }
+    }
+
+    /// **O(log(n))**, set `leaf[i] = x`, and update lazy segment tree.
+    pub fn update(&mut self, i: usize, x: T) -> Option<T> {
+        self.update_with(i, |_| x)
+    }
+    /// **O(log(n))**, update `leaf[i]` by `f(leaf[i])`, and update lazy segment tree.
+    pub fn update_with<G>(&mut self, i: usize, f: G) -> Option<T>
+    where
+        G: FnOnce(&T) -> T,
+    {
+        (i < self.len()).then(|| {
+            let leaf = self.segment_tree.leaf_offset() + i;
+            for h in (1..=self.height()).rev() {
+                self.push(leaf >> h);
+            }
+            let mut result = f(&self.segment_tree.tree[leaf]);
+            std::mem::swap(&mut self.segment_tree.tree[leaf], &mut result);
+            for h in 1..=self.height() {
+                self.pull(leaf >> h);
+            }
+            result
+        })
+    }
+
+    /// **O(log(n))**, apply `action` to all leaves in the range.
+    pub fn apply<R>(&mut self, range: R, action: F)
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = self.segment_tree.indices(range);
+        if start >= end {
+            return;
+        }
+        let leaf_offset = self.segment_tree.leaf_offset();
+        let (left, right) = (leaf_offset + start, leaf_offset + end);
+        self.push_ancestors(left);
+        self.push_ancestors(right);
+
+        let (mut l, mut r) = (left, right);
+        while l < r {
+            if l % 2 == 1 {
+                self.apply_node(l, &action);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                self.apply_node(r, &action);
+            }
+            l /= 2;
+            r /= 2;
+        }
+
+        for h in 1..=self.height() {
+            if (left >> h) << h != left {
+                self.pull(left >> h);
+            }
+            if (right >> h) << h != right {
+                self.pull((right - 1) >> h);
+            }
+        }
+    }
+
+    /// **O(log(n))**, fold the range.
+    pub fn fold<R>(&mut self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = self.segment_tree.indices(range);
+        if start >= end {
+            return T::unit();
+        }
+        let leaf_offset = self.segment_tree.leaf_offset();
+        let (mut left, mut right) = (leaf_offset + start, leaf_offset + end);
+        self.push_ancestors(left);
+        self.push_ancestors(right);
+
+        let tree = &self.segment_tree.tree;
+        let (mut left_res, mut right_res) = (T::unit(), T::unit());
+        while left < right {
+            if left % 2 == 1 {
+                left_res = T::op(left_res, tree[left].clone());
+                left += 1;
+            }
+            if right % 2 == 1 {
+                right -= 1;
+                right_res = T::op(tree[right].clone(), right_res);
+            }
+            left /= 2;
+            right /= 2;
+        }
+        T::op(left_res, right_res)
+    }
+
+    /// **O(log(n))**, get the element at `i` with the postponed actions of its ancestors applied.
+    pub fn get(&self, i: usize) -> Option<T> {
+        (i < self.len()).then(|| {
+            let leaf = self.segment_tree.leaf_offset() + i;
+            (1..=self.height()).fold(self.segment_tree.tree[leaf].clone(), |x, h| {
+                self.lazy[leaf >> h].act(x, 1)
+            })
+        })
+    }
+    /// **O(n)**, collect the elements with the postponed actions applied, without propagating them.
+    pub fn to_vec(&self) -> Vec<T> {
+        let leaf_offset = self.segment_tree.leaf_offset();
+        // composed pending actions from the root, older actions of the descendants first
+        let mut pending = Vec::with_capacity(leaf_offset);
+        pending.push(F::unit());
+        for node in 1..leaf_offset {
+            pending.push(F::op(self.lazy[node].clone(), pending[node / 2].clone()));
+        }
+        (leaf_offset..leaf_offset + self.len())
+            .map(|leaf| pending[leaf / 2].act(self.segment_tree.tree[leaf].clone(), 1))
+            .collect()
+    }
+    /// **O(n)**, propagate all postponed actions and convert into the underlying segment tree.
+    pub fn into_segment_tree(mut self) -> SegmentTree<T> {
+        self.push_all();
+        self.segment_tree
+    }
+}
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> IntoIterator for LazySegmentTree<T, F> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_segment_tree().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        op::{Max, Min, Overwrite, Sum},
+        OptionMonoid,
+    };
+
+    use super::*;
+
+    fn naive_fold<T: Monoid + Clone>(data: &[T], range: Range<usize>) -> T {
+        data[range].iter().cloned().fold(T::unit(), T::op)
+    }
+
+    #[test]
+    fn test_range_add() {
+        let data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let mut sum_tree: LazySegmentTree<_, Sum<i64>> = data.into_iter().map(Sum).collect();
+        assert_eq!(sum_tree.fold(3..5).0, 7);
+        assert_eq!(sum_tree.fold(..).0, 55);
+        sum_tree.apply(2..7, Sum(10));
+        assert_eq!(sum_tree.fold(3..5).0, 27);
+        assert_eq!(sum_tree.fold(..).0, 105);
+        assert_eq!(sum_tree.fold(7..).0, 34);
+        sum_tree.apply(.., Sum(-1));
+        assert_eq!(sum_tree.fold(..).0, 94);
+        assert_eq!(sum_tree.fold(6..=7).0, 21);
+        sum_tree.update(6, Sum(0));
+        assert_eq!(sum_tree.fold(6..=7).0, 6);
+        assert_eq!(sum_tree.fold(5..5).0, 0);
+    }
+
+    #[test]
+    fn test_range_assign() {
+        let data = [2, -5, 122, -33, -12, 14, -55, 500, 3];
+        let mut max_tree: LazySegmentTree<_, OptionMonoid<Overwrite<_>>> =
+            data.into_iter().map(Max).collect();
+        assert_eq!(max_tree.fold(..).0, 500);
+        max_tree.apply(6..8, OptionMonoid::from(Overwrite(Max(0))));
+        assert_eq!(max_tree.fold(..).0, 122);
+        max_tree.apply(1..3, OptionMonoid::from(Overwrite(Max(-100))));
+        assert_eq!(max_tree.fold(..3).0, 2);
+        assert_eq!(max_tree.fold(..).0, 14);
+        max_tree.apply(0..0, OptionMonoid::from(Overwrite(Max(1000))));
+        assert_eq!(max_tree.fold(..).0, 14);
+
+        let mut sum_tree: LazySegmentTree<_, OptionMonoid<Overwrite<_>>> =
+            data.into_iter().map(Sum).collect();
+        sum_tree.apply(..4, OptionMonoid::from(Overwrite(Sum(1))));
+        sum_tree.apply(2..6, OptionMonoid::from(Overwrite(Sum(2))));
+        assert_eq!(sum_tree.fold(..).0, 1 + 1 + 2 + 2 + 2 + 2 - 55 + 500 + 3);
+        assert_eq!(sum_tree.to_vec(), [1, 1, 2, 2, 2, 2, -55, 500, 3].map(Sum));
+    }
+
+    #[test]
+    fn test_random_operations() {
+        let mut data: Vec<_> = (0..100).map(|i| Min((i * 37) % 101)).collect();
+        let mut min_tree: LazySegmentTree<_, Min<i64>> = data.iter().cloned().collect();
+        for i in 0..100 {
+            let (l, r) = ((i * 13) % 100, (i * 29) % 100 + 1);
+            let (l, r) = (l.min(r), l.max(r));
+            let action = Min((i as i64 * 7) % 50);
+            min_tree.apply(l..r, action);
+            data[l..r].iter_mut().for_each(|x| *x = action.act(*x, 1));
+            assert_eq!(min_tree.fold(l / 2..r), naive_fold(&data, l / 2..r));
+            assert_eq!(min_tree.fold(..), naive_fold(&data, 0..100));
+            assert_eq!(min_tree.get(i), Some(data[i]));
+        }
+        assert_eq!(min_tree.to_vec(), data);
+        assert_eq!(min_tree.into_iter().collect::<Vec<_>>(), data);
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let mut empty = LazySegmentTree::<Sum<u64>, Sum<u64>>::from(vec![]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        empty.apply(.., Sum(1));
+        assert_eq!(empty.fold(..), Sum(0));
+        assert_eq!(empty.get(0), None);
+        assert_eq!(empty.to_vec(), []);
+    }
+
+    #[test]
+    fn test_eq_pending_actions() {
+        let mut lazy: LazySegmentTree<_, Sum<u64>> = (0..5).map(Sum).collect();
+        let mut pushed: LazySegmentTree<_, Sum<u64>> = (0..5).map(Sum).collect();
+        lazy.apply(1..4, Sum(10));
+        pushed.apply(1..4, Sum(10));
+        pushed.fold(1..2);
+        pushed.fold(2..3);
+        assert_ne!(lazy.lazy, pushed.lazy);
+        assert_eq!(lazy, pushed);
+        assert_eq!(lazy, LazySegmentTree::from(lazy.to_vec()));
+        assert_ne!(lazy, LazySegmentTree::from(vec![Sum(0); 5]));
+    }
+
+    #[test]
+    fn test_get() {
+        let mut sum_tree: LazySegmentTree<_, Sum<u64>> = (0..5).map(Sum).collect();
+        sum_tree.apply(1..4, Sum(10));
+        assert_eq!(sum_tree.get(0), Some(Sum(0)));
+        assert_eq!(sum_tree.get(2), Some(Sum(12)));
+        assert_eq!(sum_tree.get(5), None);
+        assert_eq!(
+            sum_tree.to_vec(),
+            [Sum(0), Sum(11), Sum(12), Sum(13), Sum(4)]
+        );
+        assert_eq!(
+            sum_tree.into_segment_tree()[..],
+            [Sum(0), Sum(11), Sum(12), Sum(13), Sum(4)]
+        );
+    }
+}
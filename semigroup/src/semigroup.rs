@@ -27,6 +27,85 @@ use crate::Annotated;
 /// assert_eq!(a.semigroup(b).semigroup(c), ExampleStruct { str: Some("ten"), boolean: false, sum: 111 });
 /// ```
 ///
+/// Fields can also be operated without wrapper types.
+/// - `skip`: keep the base value, such as handles, caches or secrets.
+/// - `op`: operate by a function `fn(T, T) -> T`.
+/// - `unit`: the identity of the field with `monoid`, required for fields with `op`.
+///
+/// Structs with skipped fields cannot be `monoid`, because the unit would be only a right identity.
+/// ```
+/// use semigroup::Semigroup;
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(with = "semigroup::op::Coalesce")]
+/// pub struct Client<'a> {
+///     pub url: Option<&'a str>,
+///     #[semigroup(skip)]
+///     pub secret: &'a str,
+///     #[semigroup(op = "std::cmp::max")]
+///     pub retry: u32,
+/// }
+///
+/// let a = Client { url: None, secret: "a", retry: 3 };
+/// let b = Client { url: Some("https://example.com"), secret: "b", retry: 1 };
+///
+/// assert_eq!(a.semigroup(b), Client { url: Some("https://example.com"), secret: "a", retry: 3 });
+/// ```
+///
+/// Enums are operated field by field when both values have the same variant.
+/// When the variants differ, `mismatch` attribute selects `"priority"` or `"error"`,
+/// and it is required for enums with two or more non-unit variants.
+/// With `"priority"`, the variant declared later absorbs the earlier one, like `max` of the derived [`Ord`],
+/// so that the operation is still associative.
+/// With `"error"`, the enum derives [`crate::TrySemigroup`] with `try` attribute,
+/// and different variants fail with [`crate::VariantMismatch`].
+///
+/// Keeping `base` or `other` on mismatch is not offered, because it is not associative:
+/// `(A1 · B) · A2 = A1 · A2` by keeping `base`, but `A1 · (B · A2) = A1 · B = A1`.
+/// Keeping `other` breaks it symmetrically.
+/// ```
+/// use semigroup::Semigroup;
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(mismatch = "priority", with = "semigroup::op::Coalesce")]
+/// pub enum Auth<'a> {
+///     Basic { user: Option<&'a str>, password: Option<&'a str> },
+///     Token { token: Option<&'a str> },
+/// }
+///
+/// let a = Auth::Basic { user: Some("alice"), password: None };
+/// let b = Auth::Basic { user: Some("bob"), password: Some("secret") };
+/// let c = Auth::Token { token: Some("token") };
+///
+/// // #[test]
+/// semigroup::assert_semigroup!(&a, &b, &c);
+/// assert_eq!(a.clone().semigroup(b.clone()), Auth::Basic { user: Some("alice"), password: Some("secret") });
+/// assert_eq!(b.clone().semigroup(c.clone()), Auth::Token { token: Some("token") });
+/// assert_eq!(c.semigroup(b), Auth::Token { token: Some("token") });
+/// ```
+///
+/// ```
+/// use semigroup::{TrySemigroup, Semigroup};
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(try, mismatch = "error", with = "semigroup::op::Coalesce")]
+/// pub enum Auth<'a> {
+///     Basic { user: Option<&'a str>, password: Option<&'a str> },
+///     Token { token: Option<&'a str> },
+/// }
+///
+/// let a = Auth::Basic { user: Some("alice"), password: None };
+/// let b = Auth::Basic { user: None, password: Some("secret") };
+/// let c = Auth::Token { token: Some("token") };
+///
+/// assert_eq!(a.clone().try_semigroup(b).unwrap(), Auth::Basic { user: Some("alice"), password: Some("secret") });
+/// let err = a.try_semigroup(c).unwrap_err();
+/// assert_eq!(err.to_string(), "failed to operate fields: different variants `Basic` and `Token` of `Auth`");
+/// ```
+///
+/// Structs that borrow data by [`std::borrow::Cow`] can also derive [`crate::IntoOwned`] with `into_owned` attribute,
+/// so that they are merged without cloning and then materialized once.
+///
+/// Structs and enums whose fields may conflict can derive [`crate::TrySemigroup`] instead with `try` attribute,
+/// then errors of fields are aggregated into [`crate::FieldErrors`].
+///
 /// # Construction
 /// [`Semigroup`] can be constructed by [`crate::Construction`].
 ///
@@ -67,13 +146,18 @@ pub trait Semigroup {
 /// [`AnnotatedSemigroup`] is a [`Semigroup`] that has an annotation.
 pub trait AnnotatedSemigroup<A>: Sized + Semigroup {
     fn annotated_op(base: Annotated<Self, A>, other: Annotated<Self, A>) -> Annotated<Self, A>;
+    /// Whether this value offers something to the operation, `false` for an empty value such as `Coalesce(None)`.
+    /// It is used by [`crate::Provenance`] to tell which sources are shadowed.
+    fn offers_value(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(any(test, feature = "test"))]
 pub mod test_semigroup {
     use std::fmt::Debug;
 
-    use rand::seq::IndexedRandom;
+    use rand::{rngs::StdRng, seq::index::sample, SeedableRng};
 
     use crate::{
         commutative::test_commutative::{assert_reverse_associative_law, assert_reverse_reverse},
@@ -86,6 +170,9 @@ pub mod test_semigroup {
     ///
     /// # Usage
     /// - 1 argument: iterator of more than 3 items that implements [`Semigroup`].
+    ///   3 items are picked at random, seeded by [`SEED_ENV`] environment variable if set.
+    /// - 1 argument and `seed = <u64>`: 3 items are picked at random by the given seed.
+    /// - 1 argument and `exhaustive`: all ordered triples of items are checked, including repeated items such as `(a, a, b)`.
     /// - More than 3 arguments: items that implements [`Semigroup`].
     ///
     /// # Examples
@@ -99,10 +186,13 @@ pub mod test_semigroup {
     ///
     /// let v = vec![a, b, c];
     /// assert_semigroup!(&v);
+    /// assert_semigroup!(&v, seed = 42);
+    /// assert_semigroup!(&v, exhaustive);
     /// ```
     ///
     /// # Panics
     /// - If the given function does not satisfy the *semigroup* property.
+    ///   The seed and the indices of picked items are reported to stderr.
     /// - The input iterator has less than 3 items.
     ///
     /// ```compile_fail
@@ -113,6 +203,18 @@ pub mod test_semigroup {
     /// ```
     #[macro_export]
     macro_rules! assert_semigroup {
+        ($v:expr, seed = $seed:expr) => {
+            $crate::assert_semigroup!(@pick $v, $crate::test_semigroup::Pick::Seed($seed))
+        };
+        ($v:expr, exhaustive) => {
+            $crate::assert_semigroup!(@pick $v, $crate::test_semigroup::Pick::Exhaustive)
+        };
+        (@pick $v:expr, $pick:expr) => {
+            $crate::test_semigroup::for_each_pick3($v, $pick, |a, b, c| {
+                $crate::test_semigroup::assert_semigroup_impl(a.clone(), b.clone(), c.clone());
+                $crate::test_monoid::assert_option_monoid(a.clone(), b.clone(), c.clone());
+            })
+        };
         ($a:expr, $b: expr, $($tail: expr),*) => {
             {
                 let v = vec![$a, $b, $($tail),*];
@@ -120,18 +222,94 @@ pub mod test_semigroup {
             }
         };
         ($v:expr) => {
-            {
-                let (a, b, c) = $crate::test_semigroup::pick3($v);
-                $crate::test_semigroup::assert_semigroup_impl(a.clone(), b.clone(), c.clone());
-                $crate::test_monoid::assert_option_monoid(a.clone(), b.clone(), c.clone());
-            }
+            $crate::assert_semigroup!(@pick $v, $crate::test_semigroup::Pick::Random)
         };
     }
 
+    /// Environment variable to fix the seed of picking items by law assertion macros such as [`crate::assert_semigroup!`].
+    pub const SEED_ENV: &str = "SEMIGROUP_SEED";
+
+    /// How law assertion macros pick 3 items from given data.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+    pub enum Pick {
+        /// Pick at random, seeded by [`SEED_ENV`] environment variable if set.
+        #[default]
+        Random,
+        /// Pick at random by the given seed.
+        Seed(u64),
+        /// Pick all ordered triples of items, including repeated ones.
+        Exhaustive,
+    }
+
+    /// Return the seed given by [`SEED_ENV`] environment variable, or random one.
+    pub fn seed() -> u64 {
+        std::env::var(SEED_ENV).map_or_else(
+            |_| rand::random(),
+            |s| {
+                s.parse()
+                    .unwrap_or_else(|_| panic!("{SEED_ENV} must be u64, but got `{s}`"))
+            },
+        )
+    }
+
     pub fn pick3<T: Clone>(data: &[T]) -> (T, T, T) {
-        data.choose_multiple_array(&mut rand::rng())
-            .map(|[a, b, c]| (a, b, c))
-            .expect("failed to pick 3 items")
+        let [i, j, k] = pick3_indices(data.len(), seed());
+        (data[i].clone(), data[j].clone(), data[k].clone())
+    }
+
+    pub fn pick3_indices(len: usize, seed: u64) -> [usize; 3] {
+        assert!(len >= 3, "failed to pick 3 items");
+        let indices = sample(&mut StdRng::seed_from_u64(seed), len, 3);
+        [indices.index(0), indices.index(1), indices.index(2)]
+    }
+
+    /// Call `f` for each triple picked from `data` by `pick`.
+    /// When `f` panics, the seed and the indices of the triple are reported to stderr.
+    pub fn for_each_pick3<T: Clone>(data: &[T], pick: Pick, f: impl Fn(T, T, T)) {
+        let len = data.len();
+        let (triples, seed) = match pick {
+            Pick::Random | Pick::Seed(_) => {
+                let seed = if let Pick::Seed(seed) = pick {
+                    seed
+                } else {
+                    seed()
+                };
+                (vec![pick3_indices(len, seed)], Some(seed))
+            }
+            Pick::Exhaustive => {
+                assert!(len >= 3, "failed to pick 3 items");
+                let triples = (0..len)
+                    .flat_map(|i| (0..len).flat_map(move |j| (0..len).map(move |k| [i, j, k])))
+                    .collect();
+                (triples, None)
+            }
+        };
+        for indices in triples {
+            let _report = PickReport { indices, len, seed };
+            let [i, j, k] = indices;
+            f(data[i].clone(), data[j].clone(), data[k].clone());
+        }
+    }
+
+    struct PickReport {
+        indices: [usize; 3],
+        len: usize,
+        seed: Option<u64>,
+    }
+    impl Drop for PickReport {
+        fn drop(&mut self) {
+            if std::thread::panicking() {
+                let Self { indices, len, seed } = self;
+                match seed {
+                    Some(seed) => eprintln!(
+                        "law assertion failed with items {indices:?} of {len} picked by seed {seed}, rerun with {SEED_ENV}={seed}"
+                    ),
+                    None => eprintln!(
+                        "law assertion failed with items {indices:?} of {len} in exhaustive mode"
+                    ),
+                }
+            }
+        }
     }
 
     pub fn assert_semigroup_impl<T: Semigroup + Clone + PartialEq + Debug>(a: T, b: T, c: T) {
@@ -147,3 +325,65 @@ pub mod test_semigroup {
         assert_eq!(ab_c, a_bc);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::op::Sum;
+
+    use super::{test_semigroup::*, *};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Sub(i32);
+    impl Semigroup for Sub {
+        fn op(base: Self, other: Self) -> Self {
+            Self(base.0 - other.0)
+        }
+    }
+
+    #[test]
+    fn test_pick3_seed() {
+        for seed in 0..100 {
+            let indices = pick3_indices(5, seed);
+            assert_eq!(indices, pick3_indices(5, seed));
+            let [i, j, k] = indices;
+            assert!(i != j && j != k && k != i && i.max(j).max(k) < 5);
+        }
+    }
+
+    #[test]
+    fn test_for_each_pick3() {
+        let data = [1, 2, 3, 4];
+        let count = Cell::new(0);
+        for_each_pick3(&data, Pick::Exhaustive, |_, _, _| {
+            count.set(count.get() + 1)
+        });
+        assert_eq!(count.get(), 4 * 4 * 4);
+
+        let picked = Cell::new(None);
+        for_each_pick3(&data, Pick::Seed(42), |a, b, c| picked.set(Some([a, b, c])));
+        let [i, j, k] = pick3_indices(data.len(), 42);
+        assert_eq!(picked.get(), Some([data[i], data[j], data[k]]));
+    }
+
+    #[test]
+    fn test_assert_semigroup_exhaustive() {
+        let v: Vec<_> = (0..5).map(Sum).collect();
+        crate::assert_semigroup!(&v, exhaustive);
+        crate::assert_semigroup!(&v, seed = 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed")]
+    fn test_assert_semigroup_exhaustive_failure() {
+        let v = vec![Sub(0), Sub(0), Sub(0), Sub(1)];
+        crate::assert_semigroup!(&v, exhaustive);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to pick 3 items")]
+    fn test_assert_semigroup_too_few() {
+        crate::assert_semigroup!(&[Sum(1), Sum(2)], exhaustive);
+    }
+}
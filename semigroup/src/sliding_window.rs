@@ -0,0 +1,318 @@
+use std::{
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+use crate::Semigroup;
+
+/// [`SlidingWindow`] is a queue that maintains the fold of its data, also known as *SWAG* (sliding window aggregation).
+/// It requires the underlying operation on the data to form a [`Semigroup`],
+/// and neither [`crate::Monoid`] nor [`crate::Commutative`] is required.
+///
+/// The queue is represented by two stacks, front stack holds the suffix folds and back stack holds the prefix fold,
+/// so the fold of whole window is given by one operation.
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::Max, sliding_window::SlidingWindow};
+/// let mut max_window = SlidingWindow::new();
+/// for x in [2, -5, 122, -33, -12] {
+///     max_window.push_back(Max(x));
+/// }
+/// assert_eq!(max_window.fold(), Some(Max(122)));
+/// assert_eq!(max_window.pop_front(), Some(Max(2)));
+/// assert_eq!(max_window.pop_front(), Some(Max(-5)));
+/// assert_eq!(max_window.pop_front(), Some(Max(122)));
+/// assert_eq!(max_window.fold(), Some(Max(-12)));
+/// ```
+///
+/// Equality and hash are given by the data from the oldest, regardless of how the data are split into the stacks.
+#[derive(Debug, Clone)]
+pub struct SlidingWindow<T> {
+    front: Vec<(T, T)>, // front[i] = (data, fold of data in front[i..] from the top of stack)
+    back: Vec<T>,
+    back_fold: Option<T>,
+}
+impl<T: Semigroup + Clone> FromIterator<T> for SlidingWindow<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut window = Self::new();
+        window.extend(iter);
+        window
+    }
+}
+impl<T: Semigroup + Clone> Extend<T> for SlidingWindow<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push_back(x));
+    }
+}
+impl<T: PartialEq> PartialEq for SlidingWindow<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+impl<T: Eq> Eq for SlidingWindow<T> {}
+impl<T: Hash> Hash for SlidingWindow<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|x| x.hash(state));
+    }
+}
+impl<T> Default for SlidingWindow<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> SlidingWindow<T> {
+    /// **O(1)**, init empty sliding window.
+    pub fn new() -> Self {
+        Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_fold: None,
+        }
+    }
+    /// **O(1)**, return this sliding window's number of data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+    /// **O(1)**, check if this sliding window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// **O(n)**, clear all data.
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_fold = None;
+    }
+    /// **O(1)**, get the oldest data.
+    pub fn front(&self) -> Option<&T> {
+        self.front
+            .last()
+            .map(|(x, _)| x)
+            .or_else(|| self.back.first())
+    }
+    /// **O(1)**, get the newest data.
+    pub fn back(&self) -> Option<&T> {
+        self.back
+            .last()
+            .or_else(|| self.front.first().map(|(x, _)| x))
+    }
+    /// **O(1)**, iterate data from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.front
+            .iter()
+            .rev()
+            .map(|(x, _)| x)
+            .chain(self.back.iter())
+    }
+}
+impl<T: Semigroup + Clone> SlidingWindow<T> {
+    /// **O(1)**, push `x` as the newest data.
+    pub fn push_back(&mut self, x: T) {
+        self.back_fold = Some(match self.back_fold.take() {
+            Some(fold) => T::op(fold, x.clone()),
+            None => x.clone(),
+        });
+        self.back.push(x);
+    }
+    /// amortized **O(1)**, pop the oldest data.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            self.back_fold = None;
+            while let Some(x) = self.back.pop() {
+                let fold = match self.front.last() {
+                    Some((_, fold)) => T::op(x.clone(), fold.clone()),
+                    None => x.clone(),
+                };
+                self.front.push((x, fold));
+            }
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+    /// **O(1)**, fold all data in the window, return [`None`] if the window is empty.
+    pub fn fold(&self) -> Option<T> {
+        match (self.front.last(), &self.back_fold) {
+            (Some((_, front)), Some(back)) => Some(T::op(front.clone(), back.clone())),
+            (Some((_, front)), None) => Some(front.clone()),
+            (None, back) => back.clone(),
+        }
+    }
+}
+
+/// [`TimeSlidingWindow`] is a [`SlidingWindow`] whose data expire after the given period.
+/// Data must be pushed in chronological order.
+///
+/// # Examples
+/// Rolling p99 latency, only available with the `histogram` feature.
+/// ```
+/// # #[cfg(feature="histogram")]
+/// # {
+/// use std::time::{Duration, Instant};
+/// use semigroup::{op::HdrHistogram, sliding_window::TimeSlidingWindow};
+///
+/// let (now, mut window) = (Instant::now(), TimeSlidingWindow::new(Duration::from_secs(60)));
+/// for i in 0..1000 {
+///     let latency: HdrHistogram<u32> = [i].into_iter().collect();
+///     window.push(now + Duration::from_secs(i), latency);
+/// }
+/// assert_eq!(window.len(), 60);
+/// assert_eq!(window.fold().unwrap().value_at_quantile(0.99), 999);
+///
+/// window.expire(now + Duration::from_secs(1050));
+/// assert_eq!(window.fold().unwrap().value_at_quantile(0.99), 999);
+/// assert_eq!(window.fold().unwrap().min(), 991);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimeSlidingWindow<T> {
+    period: Duration,
+    times: VecDeque<Instant>,
+    window: SlidingWindow<T>,
+}
+impl<T> TimeSlidingWindow<T> {
+    /// **O(1)**, init empty sliding window, its data expire after `period`.
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            times: VecDeque::new(),
+            window: SlidingWindow::new(),
+        }
+    }
+    /// **O(1)**, return the period of this sliding window.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+    /// **O(1)**, return this sliding window's number of data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+    /// **O(1)**, check if this sliding window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+    /// **O(1)**, get the sliding window without time.
+    pub fn as_sliding_window(&self) -> &SlidingWindow<T> {
+        &self.window
+    }
+}
+impl<T: Semigroup + Clone> TimeSlidingWindow<T> {
+    /// amortized **O(1)**, expire old data and push `x` at `time`.
+    pub fn push(&mut self, time: Instant, x: T) {
+        self.expire(time);
+        self.times.push_back(time);
+        self.window.push_back(x);
+    }
+    /// amortized **O(1)** per expired data, pop the data pushed at or before `now - period`.
+    pub fn expire(&mut self, now: Instant) {
+        while let Some(&time) = self.times.front() {
+            if now.saturating_duration_since(time) < self.period {
+                break;
+            }
+            self.times.pop_front();
+            self.window.pop_front();
+        }
+    }
+    /// **O(1)**, fold all data in the window, return [`None`] if the window is empty.
+    pub fn fold(&self) -> Option<T> {
+        self.window.fold()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::op::{Concat, Sum};
+
+    use super::*;
+
+    #[test]
+    fn test_sliding_window_non_commutative() {
+        let mut window = SlidingWindow::new();
+        let mut naive = VecDeque::new();
+        for i in 0..100 {
+            window.push_back(Concat(vec![i]));
+            naive.push_back(i);
+            if i % 3 == 0 {
+                assert_eq!(
+                    window.pop_front().map(|c| c.0),
+                    naive.pop_front().map(|x| vec![x])
+                );
+            }
+            assert_eq!(window.len(), naive.len());
+            assert_eq!(window.front().map(|c| c.0[0]), naive.front().copied());
+            assert_eq!(window.back().map(|c| c.0[0]), naive.back().copied());
+            let expected = (!naive.is_empty()).then(|| Concat(naive.iter().copied().collect()));
+            assert_eq!(window.fold(), expected);
+        }
+        while window.pop_front().is_some() {}
+        assert!(window.is_empty());
+        assert_eq!(window.fold(), None);
+        assert_eq!(window.pop_front(), None);
+    }
+
+    #[test]
+    fn test_sliding_window_extend() {
+        let mut window: SlidingWindow<_> = (1..=10).map(Sum).collect();
+        assert_eq!(window.fold(), Some(Sum(55)));
+        window.pop_front();
+        window.extend([Sum(11)]);
+        assert_eq!(window.fold(), Some(Sum(65)));
+        window.clear();
+        assert_eq!(window, SlidingWindow::default());
+    }
+
+    #[test]
+    fn test_time_sliding_window() {
+        let now = Instant::now();
+        let mut window = TimeSlidingWindow::new(Duration::from_secs(10));
+        for i in 0..30 {
+            window.push(now + Duration::from_secs(i), Sum(i));
+            let from = i.saturating_sub(9);
+            assert_eq!(window.fold(), Some(Sum((from..=i).sum())));
+        }
+        window.expire(now + Duration::from_secs(35));
+        assert_eq!(window.len(), 4);
+        assert_eq!(window.as_sliding_window().front(), Some(&Sum(26)));
+        window.expire(now + Duration::from_secs(100));
+        assert!(window.is_empty());
+        assert_eq!(window.fold(), None);
+        assert_eq!(window.period(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_sliding_window_eq_by_data() {
+        let mut popped: SlidingWindow<_> = (0..3).map(Sum).collect();
+        popped.pop_front();
+        let pushed: SlidingWindow<_> = (1..3).map(Sum).collect();
+        assert_eq!(popped, pushed);
+        assert_eq!(popped.iter().collect::<Vec<_>>(), [&Sum(1), &Sum(2)]);
+
+        let hash = |window: &SlidingWindow<Sum<i32>>| {
+            let mut hasher = std::hash::DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&popped), hash(&pushed));
+
+        let mut other = pushed.clone();
+        other.push_back(Sum(3));
+        assert_ne!(popped, other);
+
+        let now = Instant::now();
+        let mut popped = TimeSlidingWindow::new(Duration::from_secs(10));
+        let mut pushed = TimeSlidingWindow::new(Duration::from_secs(10));
+        for i in 0..3 {
+            popped.push(now + Duration::from_secs(i), Sum(i));
+        }
+        popped.expire(now + Duration::from_secs(10));
+        for i in 1..3 {
+            pushed.push(now + Duration::from_secs(i), Sum(i));
+        }
+        assert_eq!(popped, pushed);
+    }
+}
@@ -0,0 +1,176 @@
+use std::ops::{Range, RangeBounds};
+
+use crate::{range::indices, Idempotent};
+
+/// [`SparseTable`] is a data structure for efficient range queries on static data.
+/// It requires the underlying operation on the data to form an [`Idempotent`] [`crate::Semigroup`],
+/// and [`crate::Monoid`] is not required.
+///
+/// Any range can be covered by two overlapping ranges of power of two length,
+/// and the overlap does not matter by *idempotency*.
+///
+/// # Examples
+/// ```
+/// use semigroup::{op::Min, sparse_table::SparseTable};
+/// let data = [2, -5, 122, -33, -12, 14, -55, 500, 3];
+/// let min_table: SparseTable<_> = data.into_iter().map(Min).collect();
+/// assert_eq!(min_table.fold(2..=5), Some(Min(-33)));
+/// assert_eq!(min_table.fold(..), Some(Min(-55)));
+/// assert_eq!(min_table.fold(3..3), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+pub struct SparseTable<T> {
+    table: Vec<Vec<T>>, // table[k][i] = fold(i..i + 2^k)
+}
+impl<T: Idempotent + Clone> FromIterator<T> for SparseTable<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+impl<T: Idempotent + Clone> From<Vec<T>> for SparseTable<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self::new().construct(v)
+    }
+}
+impl<T> SparseTable<T> {
+    /// **O(1)**, init empty sparse table.
+    pub fn new() -> Self {
+        let table = Vec::new();
+        Self { table }
+    }
+    /// **O(1)**, return this sparse table's number of data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.table.first().map_or(0, Vec::len)
+    }
+    /// **O(1)**, check if this sparse table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// **O(1)**, get the data as slice.
+    pub fn as_slice(&self) -> &[T] {
+        self.table.first().map_or(&[], Vec::as_slice)
+    }
+}
+impl<T: Idempotent + Clone> SparseTable<T> {
+    /// **O(n log(n))**, construct sparse table by given data.
+    fn construct(mut self, data: Vec<T>) -> Self {
+        let len = data.len();
+        self.table.push(data);
+        for k in 1..len.checked_ilog2().map_or(0, |h| h + 1) {
+            let (prev, width) = (&self.table[k as usize - 1], 1 << (k - 1));
+            let row = (0..=len - 2 * width)
+                .map(|i| T::op(prev[i].clone(), prev[i + width].clone()))
+                .collect();
+            self.table.push(row);
+        }
+        self
+    }
+
+    /// **O(1)**, fold the range, return [`None`] if the range is empty.
+    pub fn fold<R>(&self, range: R) -> Option<T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = indices(range, self.len());
+        (start < end).then(|| {
+            let k = (end - start).ilog2();
+            let row = &self.table[k as usize];
+            T::op(row[start].clone(), row[end - (1 << k)].clone())
+        })
+    }
+}
+impl<T> IntoIterator for SparseTable<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.table
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+            .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        op::{Coalesce, Gcd, Max, Min, Overwrite},
+        OptionMonoid,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_min_max() {
+        let data = [2, -5, 122, -33, -12, 14, -55, 500, 3];
+        let min_table: SparseTable<_> = data.into_iter().map(Min).collect();
+        let max_table: SparseTable<_> = data.into_iter().map(Max).collect();
+        assert_eq!(min_table.len(), 9);
+        for l in 0..data.len() {
+            for r in l + 1..=data.len() {
+                assert_eq!(
+                    min_table.fold(l..r),
+                    data[l..r].iter().copied().min().map(Min)
+                );
+                assert_eq!(
+                    max_table.fold(l..r),
+                    data[l..r].iter().copied().max().map(Max)
+                );
+            }
+            assert_eq!(min_table.fold(l..l), None);
+        }
+    }
+
+    #[test]
+    fn test_gcd() {
+        let data = [12u32, 18, 27, 36, 45, 54];
+        let gcd_table: SparseTable<_> = data.into_iter().map(Gcd).collect();
+        assert_eq!(gcd_table.fold(..2), Some(Gcd(6)));
+        assert_eq!(gcd_table.fold(1..=4), Some(Gcd(9)));
+        assert_eq!(gcd_table.fold(..), Some(Gcd(3)));
+    }
+
+    #[test]
+    fn test_non_commutative() {
+        let data = ["a", "b", "c", "d", "e", "f", "g"];
+        let coalesce_table: SparseTable<_> = data.map(|s| Coalesce(Some(s))).to_vec().into();
+        let overwrite_table: SparseTable<_> = data.map(Overwrite).to_vec().into();
+        for l in 0..data.len() {
+            for r in l + 1..=data.len() {
+                assert_eq!(coalesce_table.fold(l..r), Some(Coalesce(Some(data[l]))));
+                assert_eq!(overwrite_table.fold(l..r), Some(Overwrite(data[r - 1])));
+            }
+        }
+        let option_table = SparseTable::from(vec![
+            OptionMonoid(None),
+            OptionMonoid::from(Coalesce(Some("b"))),
+            OptionMonoid(None),
+        ]);
+        assert_eq!(
+            option_table.fold(..),
+            Some(OptionMonoid::from(Coalesce(Some("b"))))
+        );
+    }
+
+    #[test]
+    fn test_empty_table() {
+        let empty = SparseTable::<Min<i32>>::from(vec![]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.as_slice(), &[]);
+        assert_eq!(empty.fold(..), None);
+        assert_eq!(empty.into_iter().next(), None);
+    }
+
+    #[test]
+    fn test_singleton_table() {
+        let single = SparseTable::from(vec![Max(3)]);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.fold(..), Some(Max(3)));
+        assert_eq!(single.fold(1..), None);
+        assert_eq!(single.into_iter().collect::<Vec<_>>(), vec![Max(3)]);
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt::{self, Display},
+    ops::{Deref, DerefMut},
+};
+
+use crate::{Construction, Semigroup};
+
+/// [`TrySemigroup`] represents a binary operation that may fail, such as merging values that must not conflict.
+/// When it succeeds, it satisfies the same properties as [`Semigroup`].
+///
+/// Every [`Semigroup`] is a [`TrySemigroup`] that never fails.
+///
+/// # Deriving
+/// [`TrySemigroup`] can be derived like [`Semigroup`], use `try` attribute.
+/// Errors of all fields are aggregated into [`FieldErrors`] with their field paths.
+/// Enums with `mismatch = "error"` also fail by [`VariantMismatch`] when different variants are operated.
+/// ```
+/// use semigroup::{op::Unique, Semigroup, TrySemigroup};
+///
+/// #[derive(Debug, Clone, PartialEq, Semigroup)]
+/// #[semigroup(try, with = "semigroup::op::Unique")]
+/// pub struct Database {
+///     pub url: Option<String>,
+///     pub user: Option<String>,
+///     #[semigroup(with = "semigroup::op::Coalesce")]
+///     pub password: Option<String>,
+/// }
+///
+/// let a = Database { url: Some("postgres://a".to_string()), user: None, password: None };
+/// let b = Database { url: None, user: Some("alice".to_string()), password: None };
+/// let c = Database { url: Some("postgres://c".to_string()), user: Some("bob".to_string()), password: None };
+///
+/// let ab = a.try_semigroup(b).unwrap();
+/// assert_eq!(ab.url.as_deref(), Some("postgres://a"));
+/// assert_eq!(ab.user.as_deref(), Some("alice"));
+///
+/// let err = ab.try_semigroup(c).unwrap_err();
+/// assert_eq!(err.paths().collect::<Vec<_>>(), vec!["url", "user"]);
+/// ```
+pub trait TrySemigroup: Sized {
+    type Error;
+    fn try_op(base: Self, other: Self) -> Result<Self, Self::Error>;
+    fn try_semigroup(self, other: Self) -> Result<Self, Self::Error> {
+        TrySemigroup::try_op(self, other)
+    }
+}
+impl<T: Semigroup> TrySemigroup for T {
+    type Error = Infallible;
+    fn try_op(base: Self, other: Self) -> Result<Self, Self::Error> {
+        Ok(Semigroup::op(base, other))
+    }
+}
+
+/// [`TryConstruction`] lifts a [`TrySemigroup`] operation of the construction to the inner type, like [`Construction`].
+///
+/// Every [`Construction`] is a [`TryConstruction`] that never fails.
+pub trait TryConstruction<T>: TrySemigroup + From<T> + Deref<Target = T> + DerefMut {
+    fn try_lift_op(base: T, other: T) -> Result<T, Self::Error>;
+}
+impl<C: Construction<T>, T> TryConstruction<T> for C {
+    fn try_lift_op(base: T, other: T) -> Result<T, Self::Error> {
+        Ok(C::lift_op(base, other))
+    }
+}
+
+/// [`FieldErrors`] aggregates errors of fields with their paths, such as `database.url`.
+#[derive(Debug, Default)]
+pub struct FieldErrors {
+    errors: Vec<(String, Box<dyn Error + Send + Sync>)>,
+}
+impl FieldErrors {
+    /// Init empty errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Record the error of `field` if any, and return the value otherwise.
+    /// Nested [`FieldErrors`] are flattened with `field` as the prefix of their paths.
+    pub fn collect<T, E>(&mut self, field: &str, result: Result<T, E>) -> Option<T>
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        result
+            .map_err(|e| match e.into().downcast::<Self>() {
+                Ok(nested) => self
+                    .errors
+                    .extend(nested.errors.into_iter().map(|(path, e)| {
+                        let path = if path.is_empty() {
+                            field.to_string()
+                        } else {
+                            format!("{field}.{path}")
+                        };
+                        (path, e)
+                    })),
+                Err(e) => self.errors.push((field.to_string(), e)),
+            })
+            .ok()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+    /// Paths of the fields that failed, the error of the value itself has the empty path.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.errors.iter().map(|(path, _)| path.as_str())
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &(dyn Error + Send + Sync + 'static))> {
+        self.errors.iter().map(|(path, e)| (path.as_str(), &**e))
+    }
+}
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<_> = self
+            .iter()
+            .map(|(path, e)| match path {
+                "" => e.to_string(),
+                path => format!("`{path}`: {e}"),
+            })
+            .collect();
+        write!(f, "failed to operate fields: {}", errors.join(", "))
+    }
+}
+impl Error for FieldErrors {}
+impl From<VariantMismatch> for FieldErrors {
+    fn from(mismatch: VariantMismatch) -> Self {
+        Self {
+            errors: vec![(String::new(), Box::new(mismatch))],
+        }
+    }
+}
+
+/// [`VariantMismatch`] is the error of derived [`TrySemigroup`] with `mismatch = "error"`,
+/// when different variants of an enum are operated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VariantMismatch {
+    ty: &'static str,
+    base: &'static str,
+    other: &'static str,
+}
+impl VariantMismatch {
+    pub fn new(ty: &'static str, base: &'static str, other: &'static str) -> Self {
+        Self { ty, base, other }
+    }
+    /// The name of the enum.
+    pub fn ty(&self) -> &'static str {
+        self.ty
+    }
+    /// The variant of `base`.
+    pub fn base(&self) -> &'static str {
+        self.base
+    }
+    /// The variant of `other`.
+    pub fn other(&self) -> &'static str {
+        self.other
+    }
+}
+impl Display for VariantMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { ty, base, other } = self;
+        write!(f, "different variants `{base}` and `{other}` of `{ty}`")
+    }
+}
+impl Error for VariantMismatch {}
+
+#[cfg(test)]
+mod tests {
+    use crate::op::{Coalesce, Sum, Unique};
+
+    use super::*;
+
+    #[test]
+    fn test_semigroup_as_try_semigroup() {
+        assert_eq!(Sum(1).try_semigroup(Sum(2)), Ok(Sum(3)));
+        assert_eq!(
+            <Coalesce<u32> as TryConstruction<_>>::try_lift_op(None, Some(1)),
+            Ok(Some(1))
+        );
+    }
+
+    #[test]
+    fn test_field_errors() {
+        let mut nested = FieldErrors::new();
+        let conflict = Unique::try_op(Unique(Some(1)), Unique(Some(2)));
+        assert_eq!(nested.collect("port", conflict), None);
+        assert_eq!(
+            nested.collect("host", Ok::<_, Infallible>("localhost")),
+            Some("localhost")
+        );
+
+        let mut errors = FieldErrors::new();
+        assert_eq!(errors.collect("server", Err::<(), _>(nested)), None);
+        assert_eq!(errors.collect("name", Err::<(), _>("empty name")), None);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors.paths().collect::<Vec<_>>(),
+            vec!["server.port", "name"]
+        );
+        assert_eq!(
+            errors.to_string(),
+            "failed to operate fields: `server.port`: conflicting values 1 and 2, `name`: empty name"
+        );
+    }
+
+    #[test]
+    fn test_variant_mismatch() {
+        let mismatch = VariantMismatch::new("Auth", "Basic", "Token");
+        let mut errors = FieldErrors::new();
+        assert_eq!(
+            errors.collect("auth", Err::<(), _>(FieldErrors::from(mismatch))),
+            None
+        );
+        assert_eq!(errors.paths().collect::<Vec<_>>(), vec!["auth"]);
+        assert_eq!(
+            errors.to_string(),
+            "failed to operate fields: `auth`: different variants `Basic` and `Token` of `Auth`"
+        );
+        assert_eq!(
+            FieldErrors::from(mismatch).to_string(),
+            "failed to operate fields: different variants `Basic` and `Token` of `Auth`"
+        );
+    }
+}
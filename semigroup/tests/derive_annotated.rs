@@ -1,4 +1,4 @@
-use semigroup::{Annotate, Annotated, Semigroup};
+use semigroup::{Annotate, Annotated, Provenance, Semigroup};
 
 #[derive(Debug, Clone, PartialEq, Semigroup)]
 #[semigroup(annotated, with = "semigroup::op::Coalesce")]
@@ -99,6 +99,86 @@ fn test_unnamed_struct_semigroup() {
     );
 }
 
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, provenance, with = "semigroup::op::Coalesce")]
+pub struct ProvenanceStruct {
+    #[semigroup(with = "semigroup::op::Overwrite")]
+    pub name: String,
+    pub num: Option<u32>,
+}
+
+#[test]
+fn test_provenance_struct_semigroup() {
+    let file = ProvenanceStruct {
+        name: "file".to_string(),
+        num: Some(1),
+    }
+    .annotated("file");
+    let env = ProvenanceStruct {
+        name: "env".to_string(),
+        num: None,
+    }
+    .annotated("env");
+    let cli = ProvenanceStruct {
+        name: "cli".to_string(),
+        num: Some(3),
+    }
+    .annotated("cli");
+
+    let merged = file.semigroup(env).semigroup(cli);
+    assert_eq!(
+        merged.value(),
+        &ProvenanceStruct {
+            name: "cli".to_string(),
+            num: Some(1),
+        }
+    );
+    let ProvenanceStructAnnotation { name, num } = merged.annotation();
+    assert_eq!(name.winner(), &"cli");
+    assert_eq!(name.shadowed().collect::<Vec<_>>(), vec![&"file", &"env"]);
+    assert_eq!(num.winner(), &"file");
+    assert_eq!(num.shadowed().collect::<Vec<_>>(), vec![&"cli"]);
+    assert_eq!(num.sources(), name.sources());
+
+    let single = ProvenanceStruct {
+        name: "single".to_string(),
+        num: None,
+    }
+    .annotated("single");
+    assert_eq!(
+        single.into_annotation(),
+        ProvenanceStructAnnotation {
+            name: Provenance::new("single"),
+            num: Provenance::new("single"),
+        }
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(
+    annotated,
+    provenance,
+    mismatch = "priority",
+    with = "semigroup::op::Coalesce"
+)]
+pub enum ProvenanceEnum {
+    Named { value: Option<u32> },
+    Unnamed(Option<String>),
+}
+
+#[test]
+fn test_provenance_enum_semigroup() {
+    let a = ProvenanceEnum::Named { value: None }.annotated(1);
+    let b = ProvenanceEnum::Named { value: Some(2) }.annotated(2);
+    let ab = a.semigroup(b);
+    assert_eq!(ab.value(), &ProvenanceEnum::Named { value: Some(2) });
+    let ProvenanceEnumAnnotation::Named { value } = ab.annotation() else {
+        unreachable!()
+    };
+    assert_eq!(value.winner(), &2);
+    assert_eq!(value.sources(), &[1, 2]);
+}
+
 // #[derive(Debug, Clone, PartialEq, Semigroup)]
 // pub struct UnitStruct;
 // #[test]
@@ -108,3 +188,136 @@ fn test_unnamed_struct_semigroup() {
 //     assert_eq!(UnitStruct::semigroup(a.clone(), b.clone()), UnitStruct);
 //     assert_eq!(UnitStruct::semigroup(b.clone(), a.clone()), UnitStruct);
 // }
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, with = "semigroup::op::Coalesce")]
+pub struct Server {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, with = "semigroup::op::Coalesce")]
+pub struct Config {
+    pub name: Option<String>,
+    #[semigroup(nested)]
+    pub server: Server,
+}
+
+#[test]
+fn test_nested_struct_semigroup() {
+    let file = Config {
+        name: Some("file".to_string()),
+        server: Server {
+            host: None,
+            port: Some(8080),
+        },
+    }
+    .annotated("file");
+    let env = Config {
+        name: None,
+        server: Server {
+            host: Some("localhost".to_string()),
+            port: Some(80),
+        },
+    }
+    .annotated("env");
+
+    let config = file.semigroup(env);
+    assert_eq!(
+        config,
+        Annotated::new(
+            Config {
+                name: Some("file".to_string()),
+                server: Server {
+                    host: Some("localhost".to_string()),
+                    port: Some(8080),
+                },
+            },
+            ConfigAnnotation {
+                name: "file",
+                server: ServerAnnotation {
+                    host: "env",
+                    port: "file",
+                },
+            },
+        ),
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, provenance, with = "semigroup::op::Coalesce")]
+pub struct ProvenanceServer(Option<String>, Option<u16>);
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, provenance, mismatch = "priority")]
+pub enum ProvenanceDeploy {
+    Remote {
+        #[semigroup(with = "semigroup::op::Overwrite")]
+        region: String,
+        #[semigroup(nested)]
+        server: ProvenanceServer,
+    },
+    Local(#[semigroup(nested)] ProvenanceServer),
+}
+
+#[test]
+fn test_nested_provenance_enum_semigroup() {
+    let a = ProvenanceDeploy::Remote {
+        region: "us".to_string(),
+        server: ProvenanceServer(Some("a".to_string()), None),
+    }
+    .annotated("a");
+    let b = ProvenanceDeploy::Remote {
+        region: "eu".to_string(),
+        server: ProvenanceServer(None, Some(443)),
+    }
+    .annotated("b");
+    let c = ProvenanceDeploy::Local(ProvenanceServer(None, Some(80))).annotated("c");
+
+    let ab = a.clone().semigroup(b);
+    let ProvenanceDeployAnnotation::Remote { region, server } = ab.annotation() else {
+        panic!("mismatched variant");
+    };
+    assert_eq!(region.winner(), &"b");
+    assert_eq!(server.0.winner(), &"a");
+    assert_eq!(server.0.sources(), &["a", "b"]);
+    assert_eq!(server.1.winner(), &"b");
+
+    let ac = a.semigroup(c.clone());
+    assert_eq!(ac, c);
+    let ProvenanceDeployAnnotation::Local(server) = ac.annotation() else {
+        panic!("mismatched variant");
+    };
+    assert_eq!(server.1, Provenance::new("c"));
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, provenance, with = "semigroup::op::Coalesce")]
+pub struct SkipStruct {
+    pub url: Option<String>,
+    #[semigroup(skip)]
+    pub secret: String,
+}
+
+#[test]
+fn test_skip_struct_semigroup() {
+    let a = SkipStruct {
+        url: None,
+        secret: "first".to_string(),
+    }
+    .annotated("First");
+    let b = SkipStruct {
+        url: Some("https://example.com".to_string()),
+        secret: "second".to_string(),
+    }
+    .annotated("Second");
+
+    let ab = a.semigroup(b);
+    assert_eq!(ab.value().url.as_deref(), Some("https://example.com"));
+    assert_eq!(ab.value().secret, "first");
+    assert_eq!(ab.annotation().url.winner(), &"Second");
+    assert_eq!(ab.annotation().url.sources(), ["First", "Second"]);
+    // skipped field is never contributed by other sources
+    assert_eq!(ab.annotation().secret, Provenance::new("First"));
+}
@@ -0,0 +1,300 @@
+use semigroup::{
+    assert_commutative, assert_monoid, assert_semigroup, Annotate, Annotated, Monoid, Semigroup,
+    TrySemigroup, VariantMismatch,
+};
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(mismatch = "priority", with = "semigroup::op::Coalesce")]
+pub enum Auth {
+    Token {
+        token: Option<String>,
+        #[semigroup(with = "semigroup::op::Overwrite")]
+        refresh: bool,
+    },
+    Basic(Option<String>, Option<String>),
+}
+
+#[test]
+fn test_enum_semigroup_same_variant() {
+    let a = Auth::Token {
+        token: None,
+        refresh: true,
+    };
+    let b = Auth::Token {
+        token: Some("secret".to_string()),
+        refresh: false,
+    };
+    assert_eq!(
+        Auth::op(a.clone(), b.clone()),
+        Auth::Token {
+            token: Some("secret".to_string()),
+            refresh: false,
+        }
+    );
+
+    let a = Auth::Basic(Some("user".to_string()), None);
+    let b = Auth::Basic(Some("other".to_string()), Some("password".to_string()));
+    let c = Auth::Basic(None, Some("other".to_string()));
+    assert_semigroup!(&a, &b, &c);
+    assert_eq!(
+        Auth::op(a.clone(), b.clone()),
+        Auth::Basic(Some("user".to_string()), Some("password".to_string()))
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(try, mismatch = "error", with = "semigroup::op::Unique")]
+pub enum Credential {
+    Token {
+        token: Option<String>,
+        #[semigroup(skip)]
+        refresh: bool,
+    },
+    Basic(Option<String>, Option<String>),
+}
+
+#[test]
+fn test_enum_try_semigroup_mismatch_error() {
+    let a = Credential::Basic(Some("user".to_string()), None);
+    let b = Credential::Basic(None, Some("password".to_string()));
+    assert_eq!(
+        Credential::try_op(a.clone(), b).unwrap(),
+        Credential::Basic(Some("user".to_string()), Some("password".to_string()))
+    );
+
+    let c = Credential::Basic(Some("other".to_string()), None);
+    let err = Credential::try_op(a.clone(), c).unwrap_err();
+    assert_eq!(err.paths().collect::<Vec<_>>(), vec!["0"]);
+
+    let token = Credential::Token {
+        token: Some("secret".to_string()),
+        refresh: true,
+    };
+    let err = Credential::try_op(a, token).unwrap_err();
+    assert_eq!(err.paths().collect::<Vec<_>>(), vec![""]);
+    let (_, mismatch) = err.iter().next().unwrap();
+    assert_eq!(
+        mismatch.downcast_ref::<VariantMismatch>(),
+        Some(&VariantMismatch::new("Credential", "Basic", "Token"))
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Semigroup)]
+#[semigroup(mismatch = "priority", with = "semigroup::op::Coalesce")]
+pub enum Priority {
+    Low(Option<u32>),
+    Middle(Option<u32>),
+    High(Option<u32>),
+}
+
+#[test]
+fn test_enum_semigroup_mismatch_priority() {
+    let (a, b) = (Priority::Low(None), Priority::High(Some(1)));
+    assert_eq!(Priority::op(a, b), Priority::High(Some(1)));
+    assert_eq!(Priority::op(b, a), Priority::High(Some(1)));
+    assert_eq!(
+        Priority::op(Priority::Low(None), Priority::Low(Some(2))),
+        Priority::Low(Some(2))
+    );
+    assert_eq!(
+        Priority::op(
+            Priority::op(Priority::High(None), Priority::Low(Some(1))),
+            Priority::High(Some(2))
+        ),
+        Priority::High(Some(2))
+    );
+
+    let mixed = [
+        Priority::Low(None),
+        Priority::Low(Some(1)),
+        Priority::Middle(None),
+        Priority::Middle(Some(2)),
+        Priority::High(None),
+        Priority::High(Some(3)),
+    ];
+    assert_semigroup!(&mixed, exhaustive);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Semigroup)]
+#[semigroup(monoid, commutative, mismatch = "priority")]
+pub enum Level {
+    #[semigroup(unit)]
+    Unset,
+    Warn(#[semigroup(with = "semigroup::op::Max")] u32),
+    Error(#[semigroup(with = "semigroup::op::Max")] u32),
+}
+
+#[test]
+fn test_enum_monoid_mismatch_priority() {
+    let mixed = [
+        Level::Unset,
+        Level::Warn(1),
+        Level::Warn(2),
+        Level::Error(1),
+        Level::Error(2),
+    ];
+    assert_monoid!(&mixed, exhaustive);
+    assert_commutative!(&mixed, exhaustive);
+    assert_eq!(Level::op(Level::Error(1), Level::Warn(2)), Level::Error(1));
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(monoid, commutative)]
+pub enum Counter {
+    #[semigroup(unit)]
+    Empty,
+    Count(#[semigroup(with = "semigroup::op::Sum")] u64),
+}
+
+#[test]
+fn test_enum_monoid() {
+    let (a, b, c) = (Counter::unit(), Counter::Count(2), Counter::Count(3));
+    assert_monoid!(&a, &b, &c);
+    assert_eq!(Counter::unit(), Counter::Empty);
+    assert_eq!(a.semigroup(b).semigroup(c), Counter::Count(5));
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, mismatch = "priority", with = "semigroup::op::Coalesce")]
+pub enum Source {
+    File {
+        path: Option<String>,
+        #[semigroup(with = "semigroup::op::Overwrite")]
+        strict: bool,
+    },
+    Url(Option<String>),
+}
+
+#[test]
+fn test_enum_annotated_semigroup() {
+    let a = Source::File {
+        path: Some("config.json".to_string()),
+        strict: true,
+    }
+    .annotated("First");
+    let b = Source::File {
+        path: None,
+        strict: false,
+    }
+    .annotated("Second");
+    let c = Source::Url(Some("https://example.com".to_string())).annotated("Third");
+
+    let ab = Semigroup::op(a.clone(), b.clone());
+    assert_eq!(
+        ab,
+        Annotated::new(
+            Source::File {
+                path: Some("config.json".to_string()),
+                strict: false,
+            },
+            SourceAnnotation::File {
+                path: "First",
+                strict: "Second",
+            },
+        ),
+    );
+
+    let bc = Semigroup::op(b.clone(), c.clone());
+    assert_eq!(
+        bc,
+        Annotated::new(
+            Source::Url(Some("https://example.com".to_string())),
+            SourceAnnotation::Url("Third"),
+        ),
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(mismatch = "priority", with = "semigroup::op::Coalesce")]
+pub enum Connection {
+    Tcp {
+        host: Option<String>,
+        #[semigroup(skip)]
+        fd: u32,
+    },
+    Unix(Option<String>, #[semigroup(op = "std::cmp::max")] u32),
+}
+
+#[test]
+fn test_enum_field_attr_semigroup() {
+    let a = Connection::Tcp { host: None, fd: 1 };
+    let b = Connection::Tcp {
+        host: Some("localhost".to_string()),
+        fd: 2,
+    };
+    assert_eq!(
+        Connection::op(a, b),
+        Connection::Tcp {
+            host: Some("localhost".to_string()),
+            fd: 1,
+        }
+    );
+
+    let a = Connection::Unix(Some("/tmp/a.sock".to_string()), 3);
+    let b = Connection::Unix(None, 7);
+    assert_eq!(
+        Connection::op(a, b),
+        Connection::Unix(Some("/tmp/a.sock".to_string()), 7)
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, with = "semigroup::op::Coalesce")]
+pub enum AnnotatedConnection {
+    Tcp {
+        host: Option<String>,
+        #[semigroup(skip)]
+        fd: u32,
+    },
+}
+
+#[test]
+fn test_enum_annotated_skip_semigroup() {
+    let a = AnnotatedConnection::Tcp { host: None, fd: 1 }.annotated("a");
+    let b = AnnotatedConnection::Tcp {
+        host: Some("localhost".to_string()),
+        fd: 2,
+    }
+    .annotated("b");
+    let ab = a.semigroup(b);
+    assert_eq!(
+        ab.value(),
+        &AnnotatedConnection::Tcp {
+            host: Some("localhost".to_string()),
+            fd: 1,
+        }
+    );
+    assert_eq!(
+        ab.annotation(),
+        &AnnotatedConnectionAnnotation::Tcp { host: "b", fd: "a" }
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(idempotent, mismatch = "priority")]
+pub enum Origin {
+    Default(#[semigroup(with = "semigroup::op::Max")] u32),
+    Explicit {
+        #[semigroup(with = "semigroup::op::Coalesce")]
+        name: Option<&'static str>,
+        #[semigroup(skip)]
+        line: u32,
+    },
+}
+
+#[test]
+fn test_enum_idempotent() {
+    let mixed = [
+        Origin::Default(1),
+        Origin::Default(2),
+        Origin::Explicit {
+            name: None,
+            line: 1,
+        },
+        Origin::Explicit {
+            name: Some("x"),
+            line: 2,
+        },
+    ];
+    semigroup::assert_idempotent!(&mixed, exhaustive);
+}
@@ -66,3 +66,310 @@ fn test_unit_struct_semigroup() {
     assert_eq!(UnitStruct::op(a.clone(), b.clone()), UnitStruct);
     assert_eq!(UnitStruct::op(b.clone(), a.clone()), UnitStruct);
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handle(u32);
+
+fn longer(base: String, other: String) -> String {
+    if other.len() > base.len() {
+        other
+    } else {
+        base
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(with = "semigroup::op::Coalesce")]
+pub struct FieldAttrStruct {
+    pub name: Option<String>,
+    #[semigroup(skip)]
+    pub handle: Handle,
+    #[semigroup(op = "longer")]
+    pub description: String,
+    #[semigroup(op = "std::cmp::max")]
+    pub retry: u32,
+    #[semigroup(with = "semigroup::op::Min")]
+    pub timeout: u64,
+}
+
+#[test]
+fn test_field_attr_struct_semigroup() {
+    let a = FieldAttrStruct {
+        name: None,
+        handle: Handle(1),
+        description: "short".to_string(),
+        retry: 3,
+        timeout: 10,
+    };
+    let b = FieldAttrStruct {
+        name: Some("B".to_string()),
+        handle: Handle(2),
+        description: "longer one".to_string(),
+        retry: 1,
+        timeout: 5,
+    };
+    let c = FieldAttrStruct {
+        name: Some("C".to_string()),
+        handle: Handle(3),
+        description: "tiny".to_string(),
+        retry: 5,
+        timeout: 20,
+    };
+    semigroup::assert_semigroup!(&a, &b, &c);
+    assert_eq!(
+        FieldAttrStruct::op(a.clone(), b.clone()),
+        FieldAttrStruct {
+            name: Some("B".to_string()),
+            handle: Handle(1),
+            description: "longer one".to_string(),
+            retry: 3,
+            timeout: 5,
+        }
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(idempotent)]
+pub struct IdempotentStruct {
+    #[semigroup(with = "semigroup::op::Coalesce")]
+    pub name: Option<String>,
+    #[semigroup(skip)]
+    pub handle: Handle,
+    #[semigroup(with = "semigroup::op::Max")]
+    pub retry: u32,
+    pub timeout: semigroup::op::Min<u64>,
+}
+
+#[test]
+fn test_idempotent_struct() {
+    let a = IdempotentStruct {
+        name: None,
+        handle: Handle(1),
+        retry: 3,
+        timeout: semigroup::op::Min(10),
+    };
+    let b = IdempotentStruct {
+        name: Some("B".to_string()),
+        handle: Handle(2),
+        retry: 1,
+        timeout: semigroup::op::Min(5),
+    };
+    let c = IdempotentStruct {
+        name: Some("C".to_string()),
+        handle: Handle(3),
+        retry: 5,
+        timeout: semigroup::op::Min(20),
+    };
+    semigroup::assert_idempotent!(&a, &b, &c);
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(monoid, with = "semigroup::op::Coalesce")]
+pub struct FieldAttrMonoid {
+    pub name: Option<String>,
+    #[semigroup(op = "longer", unit = String::new())]
+    pub description: String,
+    #[semigroup(op = "std::cmp::max", unit = u32::MIN)]
+    pub retry: u32,
+    #[semigroup(with = "semigroup::op::Min", unit = u64::MAX)]
+    pub timeout: u64,
+}
+
+#[test]
+fn test_field_attr_monoid() {
+    let a = FieldAttrMonoid {
+        name: None,
+        description: "short".to_string(),
+        retry: 3,
+        timeout: 10,
+    };
+    let b = FieldAttrMonoid {
+        name: Some("B".to_string()),
+        description: "longer one".to_string(),
+        retry: 1,
+        timeout: 5,
+    };
+    let c = FieldAttrMonoid {
+        name: Some("C".to_string()),
+        description: "tiny".to_string(),
+        retry: 5,
+        timeout: 20,
+    };
+    semigroup::assert_monoid!(&a, &b, &c);
+    assert_eq!(
+        <FieldAttrMonoid as semigroup::Monoid>::unit(),
+        FieldAttrMonoid {
+            name: None,
+            description: String::new(),
+            retry: 0,
+            timeout: u64::MAX,
+        }
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(with = "semigroup::op::Coalesce")]
+pub struct BorrowedConfig<'a> {
+    pub host: Option<&'a str>,
+    #[semigroup(with = "semigroup::op::Overwrite")]
+    pub user: &'a str,
+    pub shared: Option<std::sync::Arc<str>>,
+    #[semigroup(with = "semigroup::op::Concat")]
+    pub paths: Vec<&'a std::path::Path>,
+}
+
+#[test]
+fn test_borrowed_struct_semigroup() {
+    let shared: std::sync::Arc<str> = "shared".into();
+    let a = BorrowedConfig {
+        host: Some("localhost"),
+        user: "alice",
+        shared: None,
+        paths: vec![std::path::Path::new("/etc")],
+    };
+    let b = BorrowedConfig {
+        host: None,
+        user: "bob",
+        shared: Some(shared.clone()),
+        paths: vec![std::path::Path::new("/home")],
+    };
+    let merged = BorrowedConfig::op(a, b);
+    assert_eq!(merged.host, Some("localhost"));
+    assert_eq!(merged.user, "bob");
+    assert!(std::sync::Arc::ptr_eq(
+        merged.shared.as_ref().unwrap(),
+        &shared
+    ));
+    assert_eq!(
+        merged.paths,
+        vec![std::path::Path::new("/etc"), std::path::Path::new("/home")]
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(into_owned, with = "semigroup::op::Coalesce")]
+pub struct CowConfig<'a> {
+    pub host: Option<std::borrow::Cow<'a, str>>,
+    pub port: Option<u16>,
+    #[semigroup(with = "semigroup::op::Concat")]
+    pub tags: Vec<std::borrow::Cow<'a, str>>,
+    #[semigroup(op = "semigroup::Semigroup::op")]
+    pub note: std::borrow::Cow<'a, str>,
+}
+
+#[test]
+fn test_cow_struct_into_owned() {
+    use semigroup::IntoOwned;
+    use std::borrow::Cow;
+
+    fn load(file: &str, env: &str) -> CowConfig<'static> {
+        let a = CowConfig {
+            host: Some(Cow::Borrowed(file)),
+            port: Some(8080),
+            tags: vec![Cow::Borrowed(file)],
+            note: Cow::Borrowed(""),
+        };
+        let b = CowConfig {
+            host: Some(Cow::Borrowed(env)),
+            port: None,
+            tags: vec![Cow::Borrowed(env)],
+            note: Cow::Borrowed(env),
+        };
+        let merged = CowConfig::op(a, b);
+        // nothing is cloned until into_owned
+        assert!(matches!(merged.host, Some(Cow::Borrowed(_))));
+        assert!(matches!(merged.note, Cow::Borrowed(_)));
+        merged.into_owned()
+    }
+
+    let (file, env) = (String::from("file"), String::from("env"));
+    let config = load(&file, &env);
+    drop((file, env));
+    assert_eq!(
+        config,
+        CowConfig {
+            host: Some("file".into()),
+            port: Some(8080),
+            tags: vec!["file".into(), "env".into()],
+            note: "env".into(),
+        }
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(try, with = "semigroup::op::Unique")]
+pub struct TryDatabase {
+    pub url: Option<String>,
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(try, with = "semigroup::op::Coalesce")]
+pub struct TryConfig {
+    pub name: Option<String>,
+    #[semigroup(nested)]
+    pub database: TryDatabase,
+    #[semigroup(with = "semigroup::op::Unique")]
+    pub port: Option<u16>,
+    #[semigroup(skip)]
+    pub version: u32,
+}
+
+#[test]
+fn test_try_struct_field_errors() {
+    use semigroup::TrySemigroup;
+
+    let a = TryConfig {
+        name: Some("a".to_string()),
+        database: TryDatabase {
+            url: Some("postgres://a".to_string()),
+            user: None,
+        },
+        port: Some(5432),
+        version: 1,
+    };
+    let b = TryConfig {
+        name: Some("b".to_string()),
+        database: TryDatabase {
+            url: None,
+            user: Some("alice".to_string()),
+        },
+        port: None,
+        version: 2,
+    };
+    let ab = a.clone().try_semigroup(b).unwrap();
+    assert_eq!(
+        ab,
+        TryConfig {
+            name: Some("a".to_string()),
+            database: TryDatabase {
+                url: Some("postgres://a".to_string()),
+                user: Some("alice".to_string()),
+            },
+            port: Some(5432),
+            version: 1,
+        }
+    );
+
+    let c = TryConfig {
+        name: None,
+        database: TryDatabase {
+            url: Some("postgres://c".to_string()),
+            user: Some("bob".to_string()),
+        },
+        port: Some(5433),
+        version: 3,
+    };
+    let err = ab.try_semigroup(c).unwrap_err();
+    assert_eq!(
+        err.paths().collect::<Vec<_>>(),
+        vec!["database.url", "database.user", "port"]
+    );
+    assert_eq!(
+        err.to_string(),
+        "failed to operate fields: \
+         `database.url`: conflicting values \"postgres://a\" and \"postgres://c\", \
+         `database.user`: conflicting values \"alice\" and \"bob\", \
+         `port`: conflicting values 5432 and 5433"
+    );
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "proptest")]
+
+use std::collections::BTreeMap;
+
+use proptest::prelude::*;
+use semigroup::{
+    monoid_laws,
+    op::{
+        Coalesce, CoalesceLast, CoalesceNonEmpty, Concat, Gcd, Lcm, Max, MergeMap, Min, Overwrite,
+        Prod, Sum, Xor,
+    },
+    semigroup_laws, Annotated, OptionMonoid, Reverse,
+};
+
+#[cfg(feature = "monoid")]
+use semigroup::op::Moments;
+#[cfg(feature = "sketch")]
+use semigroup::op::TDigest;
+
+monoid_laws!(coalesce_laws, Coalesce<u8>);
+monoid_laws!(coalesce_last_laws, CoalesceLast<u8>);
+monoid_laws!(coalesce_non_empty_laws, CoalesceNonEmpty<String>);
+monoid_laws!(concat_laws, Concat<Vec<u8>>);
+monoid_laws!(gcd_laws, Gcd<u32>);
+monoid_laws!(lcm_laws, Lcm<u32>, (1u32..64).prop_map(Lcm));
+monoid_laws!(max_laws, Max<i32>);
+monoid_laws!(min_laws, Min<i32>);
+monoid_laws!(prod_laws, Prod<i64>, (-1000i64..1000).prop_map(Prod));
+monoid_laws!(sum_laws, Sum<i64>, (-1000i64..1000).prop_map(Sum));
+monoid_laws!(xor_laws, Xor<u64>);
+semigroup_laws!(overwrite_laws, Overwrite<char>);
+semigroup_laws!(reverse_laws, Reverse<Concat<Vec<u8>>>);
+semigroup_laws!(annotated_laws, Annotated<Coalesce<u8>, String>);
+monoid_laws!(option_monoid_laws, OptionMonoid<Overwrite<u8>>);
+monoid_laws!(merge_map_laws, MergeMap<BTreeMap<u8, Concat<Vec<u8>>>>);
+
+#[cfg(feature = "histogram")]
+monoid_laws!(hdr_histogram_laws, semigroup::op::HdrHistogram<u64>);
+
+#[cfg(feature = "sketch")]
+monoid_laws!(dd_sketch_laws, semigroup::op::DDSketch);
+
+#[cfg(feature = "sketch")]
+proptest! {
+    #[test]
+    fn t_digest_approximate_laws((a, b, c) in any::<(TDigest, TDigest, TDigest)>()) {
+        use semigroup::{Monoid, Semigroup};
+
+        prop_assert_eq!(a.clone().semigroup(TDigest::unit()), a.clone());
+        prop_assert_eq!(TDigest::unit().semigroup(a.clone()), a.clone());
+        prop_assert_eq!(a.clone().semigroup(b.clone()), b.clone().semigroup(a.clone()));
+
+        let ab_c = a.clone().semigroup(b.clone()).semigroup(c.clone());
+        let a_bc = a.semigroup(b.semigroup(c));
+        prop_assert_eq!(ab_c.count(), a_bc.count());
+        prop_assert_eq!(ab_c.min(), a_bc.min());
+        prop_assert_eq!(ab_c.max(), a_bc.max());
+        for q in (0..=100).map(|q| q as f64 / 100.0) {
+            let estimated = ab_c.quantile(q);
+            let (lower, upper) = (a_bc.quantile(q - 0.05), a_bc.quantile(q + 0.05));
+            prop_assert!(lower <= estimated && estimated <= upper, "{q}: {estimated:?} not in {lower:?}..{upper:?}");
+        }
+    }
+}
+
+#[cfg(feature = "monoid")]
+proptest! {
+    #[test]
+    fn moments_approximate_laws((a, b, c) in any::<(Moments<f64>, Moments<f64>, Moments<f64>)>()) {
+        use semigroup::{Monoid, Semigroup};
+
+        prop_assert_eq!(a.semigroup(Moments::unit()), a);
+        prop_assert_eq!(Moments::unit().semigroup(a), a);
+
+        let close = |x: f64, y: f64| (x - y).abs() <= 1e-6 * 1f64.max(x.abs()).max(y.abs());
+        let (ab_c, a_bc) = (a.semigroup(b).semigroup(c), a.semigroup(b.semigroup(c)));
+        let (ab, ba) = (a.semigroup(b), b.semigroup(a));
+        for (x, y) in [(ab_c, a_bc), (ab, ba)] {
+            prop_assert_eq!(x.count(), y.count());
+            prop_assert!(close(x.mean(), y.mean()), "{x:?} != {y:?}");
+            if x.count() > 0 {
+                prop_assert!(close(x.variance(), y.variance()), "{x:?} != {y:?}");
+            }
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_strategy_with_parameters(v in any_with::<Concat<Vec<u8>>>(((0..4).into(), ()))) {
+        prop_assert!(v.0.len() < 4);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Sub(i32);
+impl semigroup::Semigroup for Sub {
+    fn op(base: Self, other: Self) -> Self {
+        Self(base.0 - other.0)
+    }
+}
+// the failure is intentional, so its seed is not persisted as a regression
+semigroup_laws!(
+    #![proptest_config(ProptestConfig { failure_persistence: None, ..ProptestConfig::default() })]
+    #[should_panic]
+    sub_laws_failure_is_detected,
+    Sub,
+    (-10i32..10).prop_map(Sub)
+);
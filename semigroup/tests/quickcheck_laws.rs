@@ -0,0 +1,34 @@
+#![cfg(feature = "quickcheck")]
+
+use std::collections::HashMap;
+
+use quickcheck::quickcheck;
+use semigroup::{
+    op::{Coalesce, CoalesceLast, Concat, Max, MergeMap, Overwrite, Xor},
+    test_monoid::assert_monoid_impl,
+    test_semigroup::assert_semigroup_impl,
+    Annotated, OptionMonoid,
+};
+
+#[test]
+fn test_quickcheck_semigroup_laws() {
+    quickcheck(assert_semigroup_impl::<Overwrite<u8>> as fn(_, _, _));
+    quickcheck(assert_semigroup_impl::<Annotated<Coalesce<u8>, String>> as fn(_, _, _));
+}
+
+#[test]
+fn test_quickcheck_monoid_laws() {
+    quickcheck(assert_monoid_impl::<Coalesce<u8>> as fn(_, _, _));
+    quickcheck(assert_monoid_impl::<CoalesceLast<u8>> as fn(_, _, _));
+    quickcheck(assert_monoid_impl::<Concat<Vec<char>>> as fn(_, _, _));
+    quickcheck(assert_monoid_impl::<Max<i32>> as fn(_, _, _));
+    quickcheck(assert_monoid_impl::<Xor<u64>> as fn(_, _, _));
+    quickcheck(assert_monoid_impl::<OptionMonoid<Overwrite<u8>>> as fn(_, _, _));
+    quickcheck(assert_monoid_impl::<MergeMap<HashMap<u8, Max<i8>>>> as fn(_, _, _));
+}
+
+#[cfg(feature = "sketch")]
+#[test]
+fn test_quickcheck_sketch_laws() {
+    quickcheck(assert_monoid_impl::<semigroup::op::DDSketch> as fn(_, _, _));
+}
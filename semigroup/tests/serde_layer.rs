@@ -0,0 +1,132 @@
+#![cfg(feature = "serde")]
+
+use semigroup::{layer::Layers, Semigroup};
+use serde::{
+    de::value::{self, MapDeserializer},
+    Deserialize,
+};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Semigroup)]
+#[semigroup(idempotent)]
+pub struct Config {
+    #[semigroup(with = "semigroup::op::Overwrite")]
+    pub host: String,
+    #[semigroup(with = "semigroup::op::Overwrite")]
+    pub port: u16,
+    #[semigroup(with = "semigroup::op::Coalesce")]
+    pub user: Option<String>,
+    pub database: Database,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Semigroup)]
+#[semigroup(idempotent, with = "semigroup::op::Overwrite")]
+pub struct Database {
+    pub url: String,
+    #[semigroup(with = "semigroup::op::Max")]
+    pub pool: u32,
+    pub mode: Mode,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    ReadOnly,
+    Replica { primary: String },
+}
+
+fn json(s: &str) -> serde_json::Deserializer<serde_json::de::StrRead<'_>> {
+    serde_json::Deserializer::from_str(s)
+}
+fn env<'a, const N: usize>(
+    pairs: [(&'a str, &'a str); N],
+) -> MapDeserializer<'a, std::array::IntoIter<(&'a str, &'a str), N>, value::Error> {
+    MapDeserializer::new(pairs.into_iter())
+}
+
+#[test]
+fn test_merge_partial_layers() {
+    let file = r#"{ "host": "localhost", "port": 8080, "user": "root",
+        "database": { "url": "postgres://db", "pool": 4, "mode": "readonly" } }"#;
+    let cli = r#"{ "port": 3000, "database": { "pool": 2, "mode": { "replica": { "primary": "db1" } } } }"#;
+    let config: Config = Layers::new()
+        .layer("file", &mut json(file))
+        .unwrap()
+        .layer("env", env([("user", "alice"), ("host", "example.com")]))
+        .unwrap()
+        .layer("cli", &mut json(cli))
+        .unwrap()
+        .merge()
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            host: "example.com".to_string(),
+            port: 3000,
+            user: Some("root".to_string()),
+            database: Database {
+                url: "postgres://db".to_string(),
+                pool: 4,
+                mode: Mode::Replica {
+                    primary: "db1".to_string()
+                },
+            },
+        }
+    );
+}
+
+#[test]
+fn test_merge_parse_string() {
+    let file = r#"{ "host": "localhost", "database": { "url": "u", "mode": "readonly" } }"#;
+    let config: Config = Layers::new()
+        .layer("file", &mut json(file))
+        .unwrap()
+        .layer("env", env([("port", "80")]))
+        .unwrap()
+        .layer("cli", &mut json(r#"{ "database": { "pool": "16" } }"#))
+        .unwrap()
+        .merge()
+        .unwrap();
+    assert_eq!((config.port, config.database.pool), (80, 16));
+}
+
+#[test]
+fn test_merge_error_points_at_layer() {
+    let file = r#"{ "host": "localhost", "port": 8080, "database": { "url": "u", "pool": 1, "mode": "readonly" } }"#;
+
+    let err = Layers::new()
+        .layer("file", &mut json(file))
+        .unwrap()
+        .layer("env", env([("port", "http")]))
+        .unwrap()
+        .merge::<Config>()
+        .unwrap_err();
+    assert_eq!(err.layer(), Some("env"));
+    assert_eq!(
+        err.to_string(),
+        r#"layer `env`: invalid value: string "http", expected u16"#
+    );
+
+    let err = Layers::new()
+        .layer("cli", &mut json(r#"{ "database": { "mode": "primary" } }"#))
+        .unwrap()
+        .layer("file", &mut json(file))
+        .unwrap()
+        .merge::<Config>()
+        .unwrap_err();
+    assert_eq!(err.layer(), Some("cli"));
+
+    let err = Layers::new()
+        .layer("file", &mut json(r#"{ "host": "localhost" }"#))
+        .unwrap()
+        .merge::<Config>()
+        .unwrap_err();
+    assert_eq!(err.layer(), None);
+    assert_eq!(err.message(), "missing field `port`");
+
+    let err = Layers::new()
+        .layer("file", &mut json("{ broken"))
+        .unwrap_err();
+    assert_eq!(err.layer(), Some("file"));
+
+    assert!(Layers::new().merge::<Config>().is_err());
+}
@@ -0,0 +1,49 @@
+use semigroup::Semigroup;
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(with = "semigroup::op::Coalesce")]
+pub struct SkipWith {
+    pub num: Option<u32>,
+    #[semigroup(skip, with = "semigroup::op::Overwrite")]
+    pub boolean: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(annotated, with = "semigroup::op::Coalesce")]
+pub struct AnnotatedOp {
+    pub num: Option<u32>,
+    #[semigroup(op = "std::cmp::max")]
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+pub struct UnitWithoutMonoid {
+    #[semigroup(unit = 0)]
+    pub count: semigroup::op::Sum<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(monoid, with = "semigroup::op::Coalesce")]
+pub struct SkipMonoid {
+    pub num: Option<u32>,
+    #[semigroup(skip)]
+    pub boolean: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(monoid, with = "semigroup::op::Coalesce")]
+pub struct OpWithoutUnit {
+    pub num: Option<u32>,
+    #[semigroup(op = "std::cmp::max")]
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(commutative, with = "semigroup::op::Sum")]
+pub struct CommutativeOp {
+    pub sum: u32,
+    #[semigroup(op = "std::cmp::max")]
+    pub count: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use semigroup::Semigroup;
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(into_owned, with = "semigroup::op::Coalesce")]
+pub enum EnumConfig<'a> {
+    Host(Option<std::borrow::Cow<'a, str>>),
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use semigroup::{op::Overwrite, Semigroup};
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(commutative)]
+pub struct PlainField {
+    #[semigroup(with = "semigroup::op::Sum")]
+    pub count: u64,
+    pub name: Overwrite<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(commutative, with = "semigroup::op::Sum")]
+pub struct WithField {
+    pub count: u64,
+    #[semigroup(with = "semigroup::op::Coalesce")]
+    pub name: Option<String>,
+}
+
+fn main() {}
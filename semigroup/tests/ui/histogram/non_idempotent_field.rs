@@ -0,0 +1,27 @@
+use semigroup::{op::Sum, Semigroup};
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(idempotent)]
+pub struct PlainField {
+    #[semigroup(with = "semigroup::op::Overwrite")]
+    pub name: String,
+    pub count: Sum<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(idempotent, with = "semigroup::op::Sum")]
+pub struct WithField {
+    pub count: u64,
+    #[semigroup(with = "semigroup::op::Coalesce")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(idempotent, with = "semigroup::op::Coalesce")]
+pub struct OpField {
+    pub name: Option<String>,
+    #[semigroup(op = "std::cmp::max")]
+    pub count: u64,
+}
+
+fn main() {}
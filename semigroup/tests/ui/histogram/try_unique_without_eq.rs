@@ -0,0 +1,13 @@
+use semigroup::Semigroup;
+
+#[derive(Debug, Clone)]
+pub struct Version(u32);
+
+#[derive(Debug, Clone, Semigroup)]
+#[semigroup(try, with = "semigroup::op::Unique")]
+pub struct Config {
+    pub host: Option<String>,
+    pub version: Option<Version>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use semigroup::Semigroup;
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(with = "semigroup::op::Coalesce")]
+pub enum Auth {
+    Token(Option<String>),
+    Basic(Option<String>, Option<String>),
+}
+
+fn main() {}
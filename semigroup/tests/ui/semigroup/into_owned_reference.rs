@@ -0,0 +1,10 @@
+use semigroup::Semigroup;
+
+#[derive(Debug, Clone, PartialEq, Semigroup)]
+#[semigroup(into_owned, with = "semigroup::op::Coalesce")]
+pub struct ReferenceField<'a> {
+    pub host: Option<&'a str>,
+    pub port: Option<u16>,
+}
+
+fn main() {}
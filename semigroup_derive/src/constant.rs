@@ -9,8 +9,16 @@ pub struct Constant {
     pub path_annotated_semigroup: Path,
     pub path_annotated: Path,
     pub path_annotate: Path,
+    pub path_provenance: Path,
     pub path_monoid: Path,
     pub path_commutative: Path,
+    pub path_idempotent: Path,
+    pub path_group: Path,
+    pub path_into_owned: Path,
+    pub path_try_semigroup: Path,
+    pub path_try_construction: Path,
+    pub path_field_errors: Path,
+    pub path_variant_mismatch: Path,
     pub path_construction_trait: Path,
     pub path_construction_annotated: Path,
     pub path_construction_monoid: Path,
@@ -28,8 +36,16 @@ impl ConstantExt for External {
             path_annotated_semigroup: parse_quote! {::semigroup::AnnotatedSemigroup},
             path_annotated: parse_quote! {::semigroup::Annotated},
             path_annotate: parse_quote! {::semigroup::Annotate},
+            path_provenance: parse_quote! {::semigroup::Provenance},
             path_monoid: parse_quote! {::semigroup::Monoid},
             path_commutative: parse_quote! {::semigroup::Commutative},
+            path_idempotent: parse_quote! {::semigroup::Idempotent},
+            path_group: parse_quote! {::semigroup::Group},
+            path_into_owned: parse_quote! {::semigroup::IntoOwned},
+            path_try_semigroup: parse_quote! {::semigroup::TrySemigroup},
+            path_try_construction: parse_quote! {::semigroup::TryConstruction},
+            path_field_errors: parse_quote! {::semigroup::FieldErrors},
+            path_variant_mismatch: parse_quote! {::semigroup::VariantMismatch},
             path_construction_trait: parse_quote! {::semigroup::Construction},
             path_construction_annotated: parse_quote! {::semigroup::ConstructionAnnotated},
             path_construction_monoid: parse_quote! {::semigroup::ConstructionMonoid},
@@ -46,8 +62,16 @@ impl ConstantExt for Internal {
             path_annotated_semigroup: parse_quote! {crate::AnnotatedSemigroup},
             path_annotated: parse_quote! {crate::Annotated},
             path_annotate: parse_quote! {crate::Annotate},
+            path_provenance: parse_quote! {crate::Provenance},
             path_monoid: parse_quote! {crate::Monoid},
             path_commutative: parse_quote! {crate::Commutative},
+            path_idempotent: parse_quote! {crate::Idempotent},
+            path_group: parse_quote! {crate::Group},
+            path_into_owned: parse_quote! {crate::IntoOwned},
+            path_try_semigroup: parse_quote! {crate::TrySemigroup},
+            path_try_construction: parse_quote! {crate::TryConstruction},
+            path_field_errors: parse_quote! {crate::FieldErrors},
+            path_variant_mismatch: parse_quote! {crate::VariantMismatch},
             path_construction_trait: parse_quote! {crate::Construction},
             path_construction_annotated: parse_quote! {crate::ConstructionAnnotated},
             path_construction_monoid: parse_quote! {crate::ConstructionMonoid},
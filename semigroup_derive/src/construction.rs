@@ -8,7 +8,7 @@ use crate::{
 };
 
 mod ast;
-mod attr;
+pub mod attr;
 
 pub fn impl_construction<C: ConstantExt>(derive: &DeriveInput) -> syn::Result<TokenStream> {
     let constant = C::constant();
@@ -44,6 +44,15 @@ mod tests {
             pub struct Sum<T: std::ops::Add>(pub T);
         },
     )]
+    #[case::construction_idempotent(
+        "construction_idempotent",
+        impl_construction::<Internal>,
+        syn::parse_quote! {
+            #[derive(ConstructionPriv)]
+            #[construction(monoid, commutative, idempotent, unit = Self(T::min_value()), unit_where = "T: num::Bounded")]
+            pub struct Max<T: Ord>(pub T);
+        },
+    )]
     #[case::construction_custom_annotation(
         "construction_custom_annotation",
         impl_construction::<External>,
@@ -18,6 +18,9 @@ impl ToTokens for OpTrait<'_> {
         self.impl_commutative()
             .iter()
             .for_each(|i| i.to_tokens(tokens));
+        self.impl_idempotent()
+            .iter()
+            .for_each(|i| i.to_tokens(tokens));
         self.impl_semigroup_with_unit_annotate()
             .into_iter()
             .for_each(|i| i.to_tokens(tokens));
@@ -115,6 +118,28 @@ impl<'a> OpTrait<'a> {
         })
     }
 
+    pub fn impl_idempotent(&self) -> Option<ItemImpl> {
+        let Self {
+            constant,
+            derive,
+            attr,
+            ..
+        } = self;
+        let Constant {
+            path_idempotent, ..
+        } = constant;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        attr.is_idempotent().then(|| {
+            parse_quote! {
+                #[automatically_derived]
+                impl #impl_generics #path_idempotent for #ident #ty_generics #where_clause {}
+            }
+        })
+    }
+
     pub fn impl_semigroup_with_unit_annotate(&self) -> Option<ItemImpl> {
         let Self {
             constant:
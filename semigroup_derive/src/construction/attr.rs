@@ -20,6 +20,9 @@ pub struct ContainerAttr {
     #[darling(default)]
     commutative: bool,
 
+    #[darling(default)]
+    idempotent: bool,
+
     annotation_type_param: Option<TypeParam>,
     annotation_where: Option<String>, // TODO Vec
     #[darling(default)]
@@ -99,6 +102,10 @@ impl ContainerAttr {
         self.commutative
     }
 
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
     pub fn unit_annotate(&self) -> Expr {
         self.unit_annotation
             .clone()
@@ -165,6 +172,18 @@ mod tests {
         },
         Err("attribute `unit` are supported only with `monoid`"),
     )]
+    #[case::idempotent(
+        syn::parse_quote! {
+            #[derive(Construction)]
+            #[construction(commutative, idempotent)]
+            pub struct Max<T: Ord>(pub T);
+        },
+        Ok(ContainerAttr {
+            commutative: true,
+            idempotent: true,
+            ..default_container_attr()
+        }),
+    )]
     fn test_construction_container_attr(
         #[case] input: DeriveInput,
         #[case] expected: Result<ContainerAttr, &str>,
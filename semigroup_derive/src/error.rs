@@ -40,18 +40,25 @@ impl Display for ConstructionError {
 
 #[derive(Debug, Clone)]
 pub enum SemigroupError {
-    UnsupportedEnum,
     UnsupportedUnion,
     OnlyAnnotated(Name),
     OnlyMonoid(Name),
+    OnlyTry(Name),
+    OnlyEnum(Name),
+    OnlyStruct(Name),
+    OnlyUnitVariant(Name),
+    DuplicateUnitVariant,
+    EnumMonoidWithoutUnit,
+    EnumWithoutMismatch,
+    MonoidWithoutUnit(Name),
+    Conflict(Name, Name),
+    NotAnnotated(Name),
+    NestedNotPath,
 }
 impl Error for SemigroupError {}
 impl Display for SemigroupError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::UnsupportedEnum => {
-                write!(f, "derive {DERIVE_SEMIGROUP} does not support enums")
-            }
             Self::UnsupportedUnion => {
                 write!(f, "derive {DERIVE_SEMIGROUP} does not support unions")
             }
@@ -61,6 +68,54 @@ impl Display for SemigroupError {
             Self::OnlyMonoid(Name(name)) => {
                 write!(f, "attribute `{name}` are supported only with `monoid`")
             }
+            Self::OnlyTry(Name(name)) => {
+                write!(f, "attribute `{name}` are supported only with `try`")
+            }
+            Self::OnlyEnum(Name(name)) => {
+                write!(f, "attribute `{name}` are supported only with enums")
+            }
+            Self::OnlyStruct(Name(name)) => {
+                write!(f, "attribute `{name}` are supported only with structs")
+            }
+            Self::OnlyUnitVariant(Name(name)) => {
+                write!(
+                    f,
+                    "attribute `{name}` are supported only with unit variants"
+                )
+            }
+            Self::DuplicateUnitVariant => {
+                write!(
+                    f,
+                    "derive {DERIVE_SEMIGROUP} supports only one `unit` variant"
+                )
+            }
+            Self::EnumMonoidWithoutUnit => {
+                write!(
+                    f,
+                    "derive {DERIVE_SEMIGROUP} for enums with `monoid` requires `unit` or a `unit` variant"
+                )
+            }
+            Self::EnumWithoutMismatch => {
+                write!(
+                    f,
+                    "derive {DERIVE_SEMIGROUP} for enums with two or more non-unit variants requires `mismatch`, `\"priority\"` or `\"error\"`"
+                )
+            }
+            Self::MonoidWithoutUnit(Name(name)) => {
+                write!(f, "attribute `{name}` requires `unit` with `monoid`")
+            }
+            Self::Conflict(Name(name), Name(with)) => {
+                write!(f, "attribute `{name}` are not supported with `{with}`")
+            }
+            Self::NotAnnotated(Name(name)) => {
+                write!(f, "attribute `{name}` are not supported with `annotated`")
+            }
+            Self::NestedNotPath => {
+                write!(
+                    f,
+                    "attribute `nested` supports only fields of derived {DERIVE_SEMIGROUP} type"
+                )
+            }
         }
     }
 }
@@ -68,12 +123,16 @@ impl Display for SemigroupError {
 #[derive(Debug, Clone)]
 pub enum PropertiesError {
     InvalidDocAttr,
+    OnlyMonoid(Name),
 }
 impl Error for PropertiesError {}
 impl Display for PropertiesError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::InvalidDocAttr => write!(f, "invalid doc attribute"),
+            Self::OnlyMonoid(Name(name)) => {
+                write!(f, "property `{name}` are supported only with `monoid`")
+            }
         }
     }
 }
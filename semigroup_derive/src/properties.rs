@@ -4,7 +4,10 @@ use syn::ItemStruct;
 
 use crate::{
     constant::ConstantExt,
-    properties::{attr::ContainerAttr, documented::Documented},
+    properties::{
+        attr::{ContainerAttr, ItemProperties},
+        documented::Documented,
+    },
 };
 
 mod attr;
@@ -15,7 +18,8 @@ pub fn impl_properties<C: ConstantExt>(
     item: &ItemStruct,
 ) -> syn::Result<TokenStream> {
     let constant = C::constant();
-    let documented = Documented::new(&constant, attr, item)?;
+    let properties = ItemProperties::new(attr, item)?;
+    let documented = Documented::new(&constant, &properties, item)?;
     Ok(documented.to_token_stream())
 }
 
@@ -66,6 +70,38 @@ mod tests {
             },
         )
     )]
+    #[case::properties_derived(
+        "properties_derived",
+        impl_properties::<Internal>,
+        (
+            syn::parse_quote! {
+                selective
+            },
+            syn::parse_quote! {
+                /// A semigroup construction that returns the minimum value.
+                /// # Properties
+                /// <!-- properties -->
+                #[derive(ConstructionPriv)]
+                #[construction(annotated, monoid, commutative, idempotent, unit = Self(T::max_value()), unit_where = "T: num::Bounded")]
+                pub struct Min<T: Ord>(pub T);
+            },
+        ),
+    )]
+    #[case::properties_group(
+        "properties_group",
+        impl_properties::<External>,
+        (
+            syn::parse_quote! {
+                monoid, commutative, group, unit = Self(0)
+            },
+            syn::parse_quote! {
+                /// A semigroup struct that returns the sum.
+                /// # Properties
+                /// <!-- properties -->
+                pub struct Sum(pub i64);
+            },
+        ),
+    )]
     fn test_derive_properties_snapshot(
         #[case] case: &str,
         #[case] f: impl Fn(&ContainerAttr, &ItemStruct) -> syn::Result<TokenStream>,
@@ -77,4 +113,32 @@ mod tests {
             insta::assert_snapshot!(case, formatted);
         });
     }
+
+    #[rstest]
+    #[case::group_without_monoid(
+        syn::parse_quote! { group },
+        syn::parse_quote! {
+            /// <!-- properties -->
+            pub struct Xor(pub u64);
+        },
+        "property `group` are supported only with `monoid`",
+    )]
+    #[case::unit_without_monoid(
+        syn::parse_quote! { unit = Self(0) },
+        syn::parse_quote! {
+            /// <!-- properties -->
+            #[derive(Construction)]
+            #[construction(commutative)]
+            pub struct Xor(pub u64);
+        },
+        "property `unit` are supported only with `monoid`",
+    )]
+    fn test_derive_properties_error(
+        #[case] attr: ContainerAttr,
+        #[case] input: ItemStruct,
+        #[case] expected: &str,
+    ) {
+        let actual = impl_properties::<External>(&attr, &input);
+        assert_eq!(actual.unwrap_err().to_string(), expected);
+    }
 }
@@ -1,28 +1,120 @@
 use darling::FromMeta;
+use proc_macro2::Span;
+use syn::{parse_quote, DeriveInput, Expr, ItemStruct};
+
+use crate::{construction, error::PropertiesError, name::var_name, semigroup};
 
 #[derive(Debug, Clone, PartialEq, FromMeta)]
-#[darling(derive_syn_parse, and_then = Self::validate)]
+#[darling(derive_syn_parse)]
 pub struct ContainerAttr {
     #[darling(default)]
     annotated: bool,
 
     #[darling(default)]
     monoid: bool,
+    unit: Option<Expr>,
 
     #[darling(default)]
     commutative: bool,
+
+    #[darling(default)]
+    idempotent: bool,
+
+    #[darling(default)]
+    group: bool,
+
+    #[darling(default)]
+    selective: bool,
+}
+/// Properties of the item, given by [`ContainerAttr`] or derived from
+/// `#[construction(...)]` and `#[semigroup(...)]` attributes of the same item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemProperties {
+    annotated: bool,
+    monoid: bool,
+    unit: Option<Expr>,
+    commutative: bool,
+    idempotent: bool,
+    group: bool,
+    selective: bool,
 }
-impl ContainerAttr {
-    pub fn validate(self) -> darling::Result<Self> {
+impl ItemProperties {
+    pub fn new(attr: &ContainerAttr, item: &ItemStruct) -> syn::Result<Self> {
+        let derive = DeriveInput::from(item.clone());
+        let construction = construction::attr::ContainerAttr::new(&derive)?;
+        let semigroup = semigroup::attr::ContainerAttr::new(&derive)?;
+
+        // derive Construction without `unit` implements Monoid by Default
+        let construction_unit =
+            (construction.is_monoid() && construction.with_monoid_impl()).then(|| {
+                construction
+                    .unit()
+                    .cloned()
+                    .unwrap_or_else(|| parse_quote! { Default::default() })
+            });
+        let properties = Self {
+            annotated: attr.annotated || construction.is_annotated() || semigroup.is_annotated(),
+            monoid: attr.monoid || construction.is_monoid() || semigroup.is_monoid(),
+            unit: attr
+                .unit
+                .clone()
+                .or(construction_unit)
+                .or_else(|| semigroup.unit().cloned()),
+            commutative: attr.commutative
+                || construction.is_commutative()
+                || semigroup.is_commutative(),
+            idempotent: attr.idempotent
+                || construction.is_idempotent()
+                || semigroup.is_idempotent(),
+            group: attr.group,
+            selective: attr.selective,
+        };
+        properties.validate()
+    }
+    pub fn validate(self) -> syn::Result<Self> {
+        let Self {
+            monoid,
+            unit,
+            group,
+            ..
+        } = &self;
+        if !monoid {
+            let err_attr_name = if unit.is_some() {
+                Some(var_name!(unit))
+            } else if *group {
+                Some(var_name!(group))
+            } else {
+                None
+            };
+            err_attr_name.map_or(Ok(()), |a| {
+                Err(syn::Error::new(
+                    Span::call_site(),
+                    PropertiesError::OnlyMonoid(a),
+                ))
+            })?;
+        }
         Ok(self)
     }
+
     pub fn is_annotated(&self) -> bool {
         self.annotated
     }
     pub fn is_monoid(&self) -> bool {
         self.monoid
     }
+    pub fn unit(&self) -> Option<&Expr> {
+        self.unit.as_ref()
+    }
     pub fn is_commutative(&self) -> bool {
         self.commutative
     }
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+    pub fn is_group(&self) -> bool {
+        self.group
+    }
+    pub fn is_selective(&self) -> bool {
+        self.selective
+    }
 }
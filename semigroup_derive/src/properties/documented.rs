@@ -4,7 +4,7 @@ use syn::ItemStruct;
 
 use crate::{
     constant::Constant,
-    properties::{attr::ContainerAttr, documented::content::Content},
+    properties::{attr::ItemProperties, documented::content::Content},
 };
 
 mod content;
@@ -23,10 +23,10 @@ impl ToTokens for Documented<'_> {
 impl<'a> Documented<'a> {
     pub fn new(
         constant: &'a Constant,
-        attr: &'a ContainerAttr,
+        properties: &'a ItemProperties,
         item: &'a ItemStruct,
     ) -> syn::Result<Self> {
-        let mut content = Content::new(constant, attr, item)?;
+        let mut content = Content::new(constant, properties, item)?;
         content.embed_properties();
 
         Ok(Self { content, item })
@@ -3,7 +3,7 @@ use syn::{parse_quote, Attribute, Expr, ExprLit, ItemStruct, Lit, Meta, MetaName
 use crate::{
     constant::Constant,
     error::PropertiesError,
-    properties::{attr::ContainerAttr, documented::table::PropertiesTable},
+    properties::{attr::ItemProperties, documented::table::PropertiesTable},
 };
 
 #[derive(Debug, Clone)]
@@ -14,12 +14,12 @@ pub struct Content<'a> {
 impl<'a> Content<'a> {
     pub fn new(
         constant: &'a Constant,
-        attr: &'a ContainerAttr,
+        properties: &'a ItemProperties,
         item: &'a ItemStruct,
     ) -> syn::Result<Self> {
         Ok(Self {
             doc: Self::doc(&item.attrs)?,
-            table: PropertiesTable::new(constant, attr, item),
+            table: PropertiesTable::new(constant, properties),
         })
     }
     pub fn doc(attrs: &[Attribute]) -> syn::Result<String> {
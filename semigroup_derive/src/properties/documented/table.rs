@@ -1,50 +1,95 @@
 use comfy_table::{presets::ASCII_MARKDOWN, CellAlignment, Table};
-use syn::ItemStruct;
+use quote::ToTokens;
+use syn::Path;
 
-use crate::{constant::Constant, properties::attr::ContainerAttr};
+use crate::{constant::Constant, properties::attr::ItemProperties};
 
 #[derive(Debug, Clone)]
 pub struct PropertiesTable<'a> {
     constant: &'a Constant,
-    attr: &'a ContainerAttr,
+    properties: &'a ItemProperties,
 }
 impl<'a> PropertiesTable<'a> {
-    pub fn new(constant: &'a Constant, attr: &'a ContainerAttr, item: &'a ItemStruct) -> Self {
-        let _ = item;
-        Self { constant, attr }
+    pub fn new(constant: &'a Constant, properties: &'a ItemProperties) -> Self {
+        Self {
+            constant,
+            properties,
+        }
     }
     pub fn table(&self) -> Table {
         let mut table = Table::new();
         table
             .load_preset(ASCII_MARKDOWN)
             .set_header(self.header())
-            .add_row(self.row());
+            .add_row(self.row())
+            .add_row(self.law_row());
         table
             .column_iter_mut() // comfy table maybe not support markdown centering?
             .for_each(|c| c.set_cell_alignment(CellAlignment::Center));
         table
     }
-    pub fn header(&self) -> [String; 3] {
+    pub fn header(&self) -> Vec<String> {
         let Constant {
             path_annotate,
             path_monoid,
             path_commutative,
+            path_idempotent,
+            path_group,
             ..
         } = self.constant;
-        [path_annotate, path_monoid, path_commutative].map(|p| {
-            format!(
-                "[`{}`]",
-                p.segments
-                    .iter()
-                    .map(|seg| seg.ident.to_string())
-                    .collect::<Vec<_>>()
-                    .join("::")
-            )
-        })
+        let mut header: Vec<_> = [
+            path_annotate,
+            path_monoid,
+            path_commutative,
+            path_idempotent,
+            path_group,
+        ]
+        .map(|p| format!("[`{}`]", Self::path(p)))
+        .into();
+        header.push("Selective".to_string());
+        if self.properties.unit().is_some() {
+            header.push(format!("[`{}::unit`]", Self::path(path_monoid)));
+        }
+        header
     }
-    pub fn row(&self) -> [&str; 3] {
-        let Self { attr, .. } = self;
-        [attr.is_annotated(), attr.is_monoid(), attr.is_commutative()].map(Self::cell)
+    pub fn row(&self) -> Vec<String> {
+        let Self { properties, .. } = self;
+        let mut row: Vec<_> = [
+            properties.is_annotated(),
+            properties.is_monoid(),
+            properties.is_commutative(),
+            properties.is_idempotent(),
+            properties.is_group(),
+            properties.is_selective(),
+        ]
+        .map(|is| Self::cell(is).to_string())
+        .into();
+        if let Some(unit) = properties.unit() {
+            row.push(format!("`{}`", Self::code(unit)));
+        }
+        row
+    }
+    /// law-test badges, the assertion macros that verify each property
+    pub fn law_row(&self) -> Vec<String> {
+        let Self { properties, .. } = self;
+        let mut row: Vec<_> = [
+            (properties.is_annotated(), None),
+            (properties.is_monoid(), Some("assert_monoid!")),
+            (properties.is_commutative(), Some("assert_commutative!")),
+            (properties.is_idempotent(), Some("assert_idempotent!")),
+            (properties.is_group(), Some("assert_group!")),
+            (properties.is_selective(), None),
+        ]
+        .map(|(is, law)| {
+            law.filter(|_| is)
+                .map(|law| format!("`{law}`"))
+                .unwrap_or_default()
+        })
+        .into();
+        if properties.unit().is_some() {
+            row.push(String::new());
+        }
+        row
     }
     pub fn cell(is: bool) -> &'a str {
         if is {
@@ -53,4 +98,26 @@ impl<'a> PropertiesTable<'a> {
             "❌"
         }
     }
+    pub fn path(path: &Path) -> String {
+        path.segments
+            .iter()
+            .map(|seg| seg.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+    pub fn code(tokens: impl ToTokens) -> String {
+        // token stream is printed with spaces between all tokens
+        [
+            (" :: ", "::"),
+            (" (", "("),
+            (" )", ")"),
+            (" . ", "."),
+            (" ,", ","),
+        ]
+        .into_iter()
+        .fold(tokens.to_token_stream().to_string(), |code, (from, to)| {
+            code.replace(from, to)
+        })
+        .replace('|', "\\|")
+    }
 }
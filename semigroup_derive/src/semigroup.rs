@@ -8,7 +8,7 @@ use crate::{
 };
 
 mod ast;
-mod attr;
+pub mod attr;
 
 pub fn impl_semigroup<C: ConstantExt>(derive: &DeriveInput) -> syn::Result<TokenStream> {
     let constant = C::constant();
@@ -66,6 +66,47 @@ mod tests {
             }
         },
     )]
+    #[case::semigroup_provenance(
+        "semigroup_provenance",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(annotated, provenance, with = "semigroup::op::Coalesce")]
+            pub struct NamedStruct {
+                #[semigroup(with = "semigroup::op::Overwrite")]
+                pub foo: String,
+                pub bar: Option<u32>,
+            }
+        },
+    )]
+    #[case::semigroup_nested(
+        "semigroup_nested",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(annotated, with = "semigroup::op::Coalesce")]
+            pub struct Config {
+                pub name: Option<String>,
+                #[semigroup(nested)]
+                pub server: config::Server<'static>,
+            }
+        },
+    )]
+    #[case::semigroup_field_attr(
+        "semigroup_field_attr",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(monoid, with = "semigroup::op::Coalesce")]
+            pub struct Config {
+                pub name: Option<String>,
+                #[semigroup(op = "std::cmp::max", unit = 0)]
+                pub retry: u32,
+                #[semigroup(with = "semigroup::op::Sum", unit = 100)]
+                pub timeout: u64,
+            }
+        },
+    )]
     #[case::semigroup_monoid(
         "semigroup_monoid",
         impl_semigroup::<External>,
@@ -78,6 +119,124 @@ mod tests {
             }
         },
     )]
+    #[case::semigroup_commutative(
+        "semigroup_commutative",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(commutative, mismatch = "priority")]
+            pub enum Aggregate<T: Ord> {
+                Count(semigroup::op::Sum<u64>),
+                Range {
+                    #[semigroup(with = "semigroup::op::Min")]
+                    min: T,
+                    #[semigroup(with = "semigroup::op::Max")]
+                    max: T,
+                },
+            }
+        },
+    )]
+    #[case::semigroup_idempotent(
+        "semigroup_idempotent",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(idempotent)]
+            pub struct Config<T> {
+                #[semigroup(with = "semigroup::op::Overwrite")]
+                pub host: String,
+                #[semigroup(with = "semigroup::op::Max")]
+                pub pool: u32,
+                #[semigroup(skip)]
+                pub handle: Handle,
+                pub database: semigroup::op::Coalesce<T>,
+            }
+        },
+    )]
+    #[case::semigroup_into_owned(
+        "semigroup_into_owned",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(into_owned, with = "semigroup::op::Coalesce")]
+            pub struct Config<'a, T: ?Sized + ToOwned> {
+                pub host: Option<std::borrow::Cow<'a, str>>,
+                pub fallback: Option<std::borrow::Cow<'a, T>>,
+                pub port: Option<u16>,
+                #[semigroup(with = "semigroup::op::Concat")]
+                pub tags: Vec<std::borrow::Cow<'a, str>>,
+                #[semigroup(with = "semigroup::op::Overwrite")]
+                pub extra: std::sync::Arc<T>,
+            }
+        },
+    )]
+    #[case::semigroup_try(
+        "semigroup_try",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(try, with = "semigroup::op::Unique")]
+            pub struct Config {
+                pub url: Option<String>,
+                #[semigroup(with = "semigroup::op::Coalesce")]
+                pub user: Option<String>,
+                #[semigroup(nested)]
+                pub database: Database,
+                #[semigroup(skip)]
+                pub version: u32,
+            }
+        },
+    )]
+    #[case::semigroup_enum(
+        "semigroup_enum",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(monoid, mismatch = "priority", with = "semigroup::op::Coalesce")]
+            pub enum Auth {
+                #[semigroup(unit)]
+                Anonymous,
+                Token {
+                    token: Option<String>,
+                    #[semigroup(with = "semigroup::op::Overwrite")]
+                    refresh: bool,
+                },
+                Basic(Option<String>, Option<String>),
+            }
+        },
+    )]
+    #[case::semigroup_enum_annotated(
+        "semigroup_enum_annotated",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(annotated, mismatch = "priority", with = "semigroup::op::Coalesce")]
+            pub enum Auth {
+                Token {
+                    token: Option<String>,
+                    #[semigroup(with = "semigroup::op::Overwrite")]
+                    refresh: bool,
+                },
+                Basic(Option<String>, Option<String>),
+            }
+        },
+    )]
+    #[case::semigroup_enum_try(
+        "semigroup_enum_try",
+        impl_semigroup::<External>,
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(try, mismatch = "error", with = "semigroup::op::Unique")]
+            pub enum Auth {
+                Token {
+                    token: Option<String>,
+                    #[semigroup(skip)]
+                    refresh: bool,
+                },
+                Basic(Option<String>, #[semigroup(with = "semigroup::op::Coalesce")] Option<String>),
+            }
+        },
+    )]
     fn test_derive_semigroup_snapshot(
         #[case] case: &str,
         #[case] f: impl Fn(&DeriveInput) -> syn::Result<TokenStream>,
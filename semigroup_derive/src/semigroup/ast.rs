@@ -1,18 +1,24 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use syn::{Data, DataEnum, DataUnion, DeriveInput};
+use syn::{Data, DataEnum, DataStruct, DataUnion, DeriveInput};
 
 use crate::{
     constant::Constant,
     error::SemigroupError,
+    name::var_name,
     semigroup::{
-        ast::struct_semigroup::{StructAnnotate, StructSemigroup},
+        ast::{
+            enum_semigroup::{EnumAnnotate, EnumSemigroup},
+            struct_semigroup::{StructAnnotate, StructSemigroup},
+        },
         attr::ContainerAttr,
     },
 };
 
+pub mod enum_semigroup;
 pub mod field_semigroup;
 pub mod struct_semigroup;
+pub mod variant_semigroup;
 
 #[derive(Debug, Clone)]
 pub enum Semigroup<'a> {
@@ -20,6 +26,10 @@ pub enum Semigroup<'a> {
         struct_semigroup: StructSemigroup<'a>,
         struct_annotate: Option<StructAnnotate<'a>>,
     },
+    Enum {
+        enum_semigroup: EnumSemigroup<'a>,
+        enum_annotate: Option<EnumAnnotate<'a>>,
+    },
 }
 impl ToTokens for Semigroup<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -31,6 +41,13 @@ impl ToTokens for Semigroup<'_> {
                 struct_semigroup.to_tokens(tokens);
                 struct_annotate.iter().for_each(|s| s.to_tokens(tokens));
             }
+            Self::Enum {
+                enum_semigroup,
+                enum_annotate,
+            } => {
+                enum_semigroup.to_tokens(tokens);
+                enum_annotate.iter().for_each(|e| e.to_tokens(tokens));
+            }
         }
     }
 }
@@ -41,10 +58,31 @@ impl<'a> Semigroup<'a> {
         attr: &'a ContainerAttr,
     ) -> syn::Result<Self> {
         match &derive.data {
-            Data::Enum(DataEnum { enum_token, .. }) => Err(syn::Error::new_spanned(
-                enum_token,
-                SemigroupError::UnsupportedEnum,
-            )),
+            Data::Enum(DataEnum { enum_token, .. }) if attr.is_into_owned() => {
+                let into_owned = attr.is_into_owned();
+                Err(syn::Error::new_spanned(
+                    enum_token,
+                    SemigroupError::OnlyStruct(var_name!(into_owned)),
+                ))
+            }
+            Data::Enum(data_enum) => {
+                let enum_semigroup = EnumSemigroup::new(constant, derive, attr, data_enum)?;
+                let enum_annotate = attr
+                    .is_annotated()
+                    .then(|| EnumAnnotate::new(constant, derive, attr, data_enum))
+                    .transpose()?;
+                Ok(Self::Enum {
+                    enum_semigroup,
+                    enum_annotate,
+                })
+            }
+            Data::Struct(DataStruct { struct_token, .. }) if attr.mismatch().is_some() => {
+                let mismatch = attr.mismatch();
+                Err(syn::Error::new_spanned(
+                    struct_token,
+                    SemigroupError::OnlyEnum(var_name!(mismatch)),
+                ))
+            }
             Data::Struct(data_struct) => {
                 let struct_semigroup = StructSemigroup::new(constant, derive, attr, data_struct)?;
                 let struct_annotate = attr
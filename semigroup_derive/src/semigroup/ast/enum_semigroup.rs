@@ -0,0 +1,481 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, ToTokens};
+use syn::{
+    ext::IdentExt, parse_quote, Arm, DataEnum, DeriveInput, Expr, ExprClosure, Ident, ItemConst,
+    ItemEnum, ItemImpl, LitStr,
+};
+
+use crate::{
+    annotation::Annotation,
+    constant::Constant,
+    error::SemigroupError,
+    semigroup::{
+        ast::{
+            struct_semigroup::{property_assertion, StructAnnotate},
+            variant_semigroup::{VariantAnnotatedOp, VariantSemigroupOp},
+        },
+        attr::{ContainerAttr, Mismatch, Property},
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct EnumSemigroup<'a> {
+    constant: &'a Constant,
+    derive: &'a DeriveInput,
+    attr: &'a ContainerAttr,
+    variant_ops: Vec<VariantSemigroupOp<'a>>,
+}
+impl ToTokens for EnumSemigroup<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if self.attr.is_try() {
+            self.impl_try_semigroup().to_tokens(tokens);
+        } else {
+            self.impl_semigroup().to_tokens(tokens);
+        }
+        self.impl_monoid().iter().for_each(|s| s.to_tokens(tokens));
+        for property in self.attr.properties() {
+            self.impl_property(property).to_tokens(tokens);
+            self.impl_property_assertion(property)
+                .iter()
+                .for_each(|s| s.to_tokens(tokens));
+        }
+    }
+}
+impl<'a> EnumSemigroup<'a> {
+    pub fn new(
+        constant: &'a Constant,
+        derive: &'a DeriveInput,
+        attr: &'a ContainerAttr,
+        data_enum: &'a DataEnum,
+    ) -> syn::Result<Self> {
+        let variant_ops =
+            VariantSemigroupOp::new_variants(constant, derive, attr, &data_enum.variants)?;
+        if let Some(duplicated) = variant_ops.iter().filter(|v| v.is_unit()).nth(1) {
+            return Err(syn::Error::new_spanned(
+                duplicated.ident(),
+                SemigroupError::DuplicateUnitVariant,
+            ));
+        }
+        // different variants meet only by `mismatch` unless one of them is the unit variant
+        if attr.mismatch().is_none() && variant_ops.iter().filter(|v| !v.is_unit()).nth(1).is_some()
+        {
+            return Err(syn::Error::new_spanned(
+                data_enum.enum_token,
+                SemigroupError::EnumWithoutMismatch,
+            ));
+        }
+        let enum_semigroup = Self {
+            constant,
+            derive,
+            attr,
+            variant_ops,
+        };
+        if attr.is_monoid() && attr.unit().is_none() && enum_semigroup.unit_variant().is_none() {
+            return Err(syn::Error::new_spanned(
+                data_enum.enum_token,
+                SemigroupError::EnumMonoidWithoutUnit,
+            ));
+        }
+        Ok(enum_semigroup)
+    }
+    pub fn unit_variant(&self) -> Option<&VariantSemigroupOp<'a>> {
+        self.variant_ops.iter().find(|v| v.is_unit())
+    }
+
+    /// different variants are operated by the variant declared later, like `max` of the derived [`Ord`],
+    /// or fail by `mismatch = "error"` that is supported only with `try`
+    pub fn impl_mismatch_arm(&self) -> Option<Arm> {
+        let Self {
+            constant:
+                Constant {
+                    path_variant_mismatch,
+                    ..
+                },
+            derive: DeriveInput { ident, .. },
+            attr,
+            variant_ops,
+        } = self;
+        let variants = variant_ops.iter().map(|v| v.ident());
+        let result: Expr = match attr.mismatch()? {
+            Mismatch::Priority => {
+                let priority = Self::impl_priority(variants);
+                let winner: Expr = parse_quote! {{
+                    let priority = #priority;
+                    if priority(&base) < priority(&other) { other } else { base }
+                }};
+                if attr.is_try() {
+                    parse_quote! { Ok(#winner) }
+                } else {
+                    winner
+                }
+            }
+            Mismatch::Error => {
+                let variant_name = Self::impl_variant_name(variants);
+                let ty = LitStr::new(&ident.unraw().to_string(), ident.span());
+                parse_quote! {{
+                    let variant_name = #variant_name;
+                    Err(#path_variant_mismatch::new(#ty, variant_name(&base), variant_name(&other)).into())
+                }}
+            }
+        };
+        Some(parse_quote! {
+            #[allow(unreachable_patterns)]
+            (base, other) => #result,
+        })
+    }
+    pub fn impl_priority<'v>(variants: impl Iterator<Item = &'v Ident>) -> ExprClosure {
+        let arms = variants.enumerate().map(|(i, ident)| -> Arm {
+            parse_quote! { Self::#ident { .. } => #i, }
+        });
+        parse_quote! {
+            |value: &Self| -> usize {
+                match value {
+                    #( #arms )*
+                }
+            }
+        }
+    }
+    pub fn impl_variant_name<'v>(variants: impl Iterator<Item = &'v Ident>) -> ExprClosure {
+        let arms = variants.map(|ident| -> Arm {
+            let name = LitStr::new(&ident.unraw().to_string(), ident.span());
+            parse_quote! { Self::#ident { .. } => #name, }
+        });
+        parse_quote! {
+            |value: &Self| -> &'static str {
+                match value {
+                    #( #arms )*
+                }
+            }
+        }
+    }
+
+    pub fn impl_semigroup(&self) -> ItemImpl {
+        let Self {
+            constant,
+            derive,
+            variant_ops,
+            ..
+        } = self;
+        let Constant { path_semigroup, .. } = constant;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let variants_op = variant_ops.iter().map(|op| op.impl_variant_semigroup_arm());
+        let unit_arms = self
+            .unit_variant()
+            .into_iter()
+            .flat_map(|op| op.impl_unit_variant_arms());
+        let mismatch_arm = self.impl_mismatch_arm();
+        parse_quote! {
+            #[automatically_derived]
+            impl #impl_generics #path_semigroup for #ident #ty_generics #where_clause {
+                fn op(base: Self, other: Self) -> Self {
+                    match (base, other) {
+                        #(#variants_op)*
+                        #(#unit_arms)*
+                        #mismatch_arm
+                    }
+                }
+            }
+        }
+    }
+    /// `try` is not supported with `monoid`, so there is no unit variant
+    pub fn impl_try_semigroup(&self) -> ItemImpl {
+        let Self {
+            constant,
+            derive,
+            variant_ops,
+            ..
+        } = self;
+        let Constant {
+            path_try_semigroup,
+            path_field_errors,
+            ..
+        } = constant;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let variants_try_op = variant_ops.iter().map(|op| op.impl_variant_try_arm());
+        let mismatch_arm = self.impl_mismatch_arm();
+        parse_quote! {
+            #[automatically_derived]
+            impl #impl_generics #path_try_semigroup for #ident #ty_generics #where_clause {
+                type Error = #path_field_errors;
+                fn try_op(base: Self, other: Self) -> Result<Self, Self::Error> {
+                    match (base, other) {
+                        #(#variants_try_op)*
+                        #mismatch_arm
+                    }
+                }
+            }
+        }
+    }
+    pub fn impl_monoid(&self) -> Option<ItemImpl> {
+        let Self {
+            constant,
+            derive,
+            attr,
+            ..
+        } = self;
+        let Constant {
+            path_monoid,
+            attr_feature_monoid,
+            ..
+        } = constant;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        (attr.is_monoid() && attr.with_monoid_impl()).then(|| {
+            let mut g = generics.clone();
+            attr.unit_where()
+                .into_iter()
+                .for_each(|w| g.make_where_clause().predicates.push(w));
+            let (impl_generics, ty_generics, where_clause) = g.split_for_impl();
+            let unit = attr.unit().cloned().unwrap_or_else(|| {
+                let variant = self.unit_variant().map(|v| v.ident());
+                parse_quote! { Self::#variant {} }
+            });
+            parse_quote! {
+                #[automatically_derived]
+                #attr_feature_monoid
+                impl #impl_generics #path_monoid for #ident #ty_generics #where_clause {
+                    fn unit() -> Self {
+                        #unit
+                    }
+                }
+            }
+        })
+    }
+    pub fn impl_property(&self, property: Property) -> ItemImpl {
+        let Self {
+            constant,
+            derive,
+            variant_ops,
+            ..
+        } = self;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let path_property = property.path(constant);
+        let mut g = generics.clone();
+        variant_ops
+            .iter()
+            .flat_map(|v| v.impl_property_predicates(property))
+            .for_each(|w| g.make_where_clause().predicates.push(w));
+        let (impl_generics, ty_generics, where_clause) = g.split_for_impl();
+        parse_quote! {
+            #[automatically_derived]
+            impl #impl_generics #path_property for #ident #ty_generics #where_clause {}
+        }
+    }
+    pub fn impl_property_assertion(&self, property: Property) -> Option<ItemConst> {
+        let Self {
+            constant,
+            derive,
+            variant_ops,
+            ..
+        } = self;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let arms: Vec<_> = variant_ops
+            .iter()
+            .filter_map(|v| v.impl_property_assertion_arm(property, ident))
+            .collect();
+        (!arms.is_empty()).then(|| {
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let assert_fields = format_ident!("assert_fields_{}", property.name().0);
+            property_assertion(
+                constant,
+                property,
+                parse_quote! {
+                    fn #assert_fields #impl_generics (value: &#ident #ty_generics) #where_clause {
+                        match value {
+                            #( #arms )*
+                            #[allow(unreachable_patterns)]
+                            _ => {}
+                        }
+                    }
+                },
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumAnnotate<'a> {
+    constant: &'a Constant,
+    derive: &'a DeriveInput,
+    attr: &'a ContainerAttr,
+    annotation_ident: Ident,
+    annotation: Annotation,
+    variant_ops: Vec<VariantAnnotatedOp<'a>>,
+}
+impl ToTokens for EnumAnnotate<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.def_annotation().to_tokens(tokens);
+        self.impl_annotated_semigroup().to_tokens(tokens);
+        self.impl_annotate().to_tokens(tokens)
+    }
+}
+impl<'a> EnumAnnotate<'a> {
+    pub fn new(
+        constant: &'a Constant,
+        derive: &'a DeriveInput,
+        attr: &'a ContainerAttr,
+        data_enum: &'a DataEnum,
+    ) -> syn::Result<Self> {
+        let annotation_ident = StructAnnotate::annotation_ident(&derive.ident);
+        let annotation = attr.annotation(constant, &annotation_ident);
+        let variant_ops =
+            VariantAnnotatedOp::new_variants(constant, derive, attr, &data_enum.variants)?;
+        Ok(Self {
+            constant,
+            derive,
+            attr,
+            annotation_ident,
+            annotation,
+            variant_ops,
+        })
+    }
+
+    pub fn def_annotation(&self) -> ItemEnum {
+        let Self {
+            derive: DeriveInput { vis, .. },
+            annotation_ident,
+            annotation,
+            variant_ops,
+            ..
+        } = self;
+        let a = &annotation.param().ident;
+        let variants = variant_ops.iter().map(|op| op.def_annotation_variant(a));
+        parse_quote! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #vis enum #annotation_ident<#a> {
+                #( #variants ),*
+            }
+        }
+    }
+
+    pub fn impl_mismatch_arm(&self) -> Option<Arm> {
+        let Self {
+            constant: Constant { path_annotated, .. },
+            attr,
+            variant_ops,
+            ..
+        } = self;
+        // `mismatch = "error"` requires `try`, that is not supported with `annotated`
+        let Some(Mismatch::Priority) = attr.mismatch() else {
+            return None;
+        };
+        let priority = EnumSemigroup::impl_priority(variant_ops.iter().map(|v| v.ident()));
+        Some(parse_quote! {
+            #[allow(unreachable_patterns)]
+            (base_value, base_annotation, other_value, other_annotation) => {
+                let priority = #priority;
+                if priority(&base_value) < priority(&other_value) {
+                    #path_annotated::new(other_value, other_annotation)
+                } else {
+                    #path_annotated::new(base_value, base_annotation)
+                }
+            }
+        })
+    }
+    pub fn impl_annotated_semigroup(&self) -> ItemImpl {
+        let Self {
+            constant,
+            derive,
+            annotation_ident,
+            annotation,
+            variant_ops,
+            ..
+        } = self;
+        let Constant {
+            path_annotated_semigroup,
+            path_annotated,
+            ..
+        } = constant;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let (_, ty_generics, _) = generics.split_for_impl();
+        let (impl_generics, annotation_type, where_clause) = annotation.split_for_impl(generics);
+        let variants_op = variant_ops
+            .iter()
+            .map(|op| op.impl_variant_annotated_arm(annotation_ident));
+        let unit_arms = variant_ops
+            .iter()
+            .filter(|op| op.is_unit())
+            .flat_map(|op| op.impl_unit_variant_arms(annotation_ident));
+        let mismatch_arm = self.impl_mismatch_arm();
+        parse_quote! {
+            #[automatically_derived]
+            impl #impl_generics #path_annotated_semigroup<#annotation_type> for #ident #ty_generics #where_clause {
+                fn annotated_op(base: #path_annotated<Self, #annotation_type>, other: #path_annotated<Self, #annotation_type>) -> #path_annotated<Self, #annotation_type> {
+                    let (base_value, base_annotation) = base.into_parts();
+                    let (other_value, other_annotation) = other.into_parts();
+                    match (base_value, base_annotation, other_value, other_annotation) {
+                        #(#variants_op)*
+                        #(#unit_arms)*
+                        #mismatch_arm
+                    }
+                }
+            }
+        }
+    }
+    pub fn impl_annotate(&self) -> ItemImpl {
+        let Self {
+            constant,
+            derive,
+            annotation_ident,
+            annotation,
+            variant_ops,
+            ..
+        } = self;
+        let Constant {
+            path_annotate,
+            path_annotated,
+            ..
+        } = constant;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let (_, ty_generics, _) = generics.split_for_impl();
+        let (impl_generics, annotation_type, where_clause) = annotation.split_for_impl(generics);
+        let a = &annotation.param().ident;
+        if variant_ops.iter().any(|op| op.has_nested()) {
+            let arms = variant_ops
+                .iter()
+                .map(|op| op.impl_nested_annotate_arm(annotation_ident));
+            parse_quote! {
+                #[automatically_derived]
+                impl #impl_generics #path_annotate<#annotation_type> for #ident #ty_generics #where_clause {
+                    type Annotation = #a;
+                    fn annotated(self, annotation: Self::Annotation) -> #path_annotated<Self, #annotation_type> {
+                        match self {
+                            #( #arms )*
+                        }
+                    }
+                }
+            }
+        } else {
+            let arms = variant_ops
+                .iter()
+                .map(|op| op.impl_annotate_arm(annotation_ident));
+            parse_quote! {
+                #[automatically_derived]
+                impl #impl_generics #path_annotate<#annotation_type> for #ident #ty_generics #where_clause {
+                    type Annotation = #a;
+                    fn annotated(self, annotation: Self::Annotation) -> #path_annotated<Self, #annotation_type> {
+                        let variant_annotation = match &self {
+                            #( #arms )*
+                        };
+                        #path_annotated::new(self, variant_annotation)
+                    }
+                }
+            }
+        }
+    }
+}
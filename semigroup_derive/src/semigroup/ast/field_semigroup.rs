@@ -1,29 +1,48 @@
-use quote::format_ident;
-use syn::{parse_quote, DeriveInput, FieldValue, Fields, Ident, Member, Stmt};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse_quote, parse_quote_spanned,
+    spanned::Spanned,
+    visit::{self, Visit},
+    visit_mut::VisitMut,
+    Data, DeriveInput, Expr, Field, FieldValue, Fields, Ident, Lifetime, Member, Path,
+    PathArguments, Stmt, Type, TypePath, WherePredicate,
+};
 
 use crate::{
     constant::Constant,
-    semigroup::attr::{ContainerAttr, FieldAttr},
+    error::SemigroupError,
+    name::var_name,
+    semigroup::{
+        ast::struct_semigroup::StructAnnotate,
+        attr::{ContainerAttr, FieldAttr, Property},
+    },
 };
 
 #[derive(Debug, Clone)]
 pub struct FieldSemigroupOp<'a> {
     constant: &'a Constant,
+    derive: &'a DeriveInput,
     container_attr: &'a ContainerAttr,
+    ty: &'a Type,
     member: Member,
     field_attr: FieldAttr,
 }
 impl<'a> FieldSemigroupOp<'a> {
     pub fn new(
         constant: &'a Constant,
-        _derive: &'a DeriveInput,
+        derive: &'a DeriveInput,
         container_attr: &'a ContainerAttr,
+        ty: &'a Type,
         member: Member,
         field_attr: FieldAttr,
     ) -> Self {
         Self {
             constant,
+            derive,
             container_attr,
+            ty,
             member,
             field_attr,
         }
@@ -38,18 +57,103 @@ impl<'a> FieldSemigroupOp<'a> {
             .iter()
             .zip(fields.members())
             .map(|(field, member)| {
+                let field_attr = FieldAttr::new(field)?;
+                // nested field of `try` is operated by its own `TrySemigroup`
+                if field_attr.is_nested()
+                    && !container_attr.is_annotated()
+                    && !container_attr.is_try()
+                {
+                    let nested = field_attr.is_nested();
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        SemigroupError::OnlyAnnotated(var_name!(nested)),
+                    ));
+                }
+                if let Some(op) = field_attr.op().filter(|_| container_attr.is_annotated()) {
+                    return Err(syn::Error::new_spanned(
+                        op,
+                        SemigroupError::NotAnnotated(var_name!(op)),
+                    ));
+                }
+                if let Some(unit) = field_attr.unit().filter(|_| !container_attr.is_monoid()) {
+                    return Err(syn::Error::new_spanned(
+                        unit,
+                        SemigroupError::OnlyMonoid(var_name!(unit)),
+                    ));
+                }
+                if field_attr.is_skip() && container_attr.is_commutative() {
+                    let (skip, commutative) =
+                        (field_attr.is_skip(), container_attr.is_commutative());
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        SemigroupError::Conflict(var_name!(skip), var_name!(commutative)),
+                    ));
+                }
+                // properties of `op` functions cannot be verified
+                if let Some((op, property)) =
+                    field_attr.op().zip(container_attr.properties().next())
+                {
+                    return Err(syn::Error::new_spanned(
+                        op,
+                        SemigroupError::Conflict(var_name!(op), property.name()),
+                    ));
+                }
+                // unit of struct is built from its fields, skipped field would be only a right identity
+                let is_struct = matches!(derive.data, Data::Struct(_));
+                if is_struct && field_attr.is_skip() && container_attr.is_monoid() {
+                    let (skip, monoid) = (field_attr.is_skip(), container_attr.is_monoid());
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        SemigroupError::Conflict(var_name!(skip), var_name!(monoid)),
+                    ));
+                }
+                if let Some(op) = field_attr.op().filter(|_| {
+                    is_struct && container_attr.is_monoid() && field_attr.unit().is_none()
+                }) {
+                    return Err(syn::Error::new_spanned(
+                        op,
+                        SemigroupError::MonoidWithoutUnit(var_name!(op)),
+                    ));
+                }
                 Ok(Self::new(
                     constant,
                     derive,
                     container_attr,
+                    &field.ty,
                     member,
-                    FieldAttr::new(field)?,
+                    field_attr,
                 ))
             })
             .collect()
     }
 
+    pub fn member(&self) -> &Member {
+        &self.member
+    }
+    /// skipped field of `other` is not used, so it is not bound
+    pub fn other_binding_pat(&self, prefix: &str) -> TokenStream {
+        let Self { member, .. } = self;
+        if self.field_attr.is_skip() {
+            quote! { #member: _ }
+        } else {
+            binding_pat(prefix, member)
+        }
+    }
     pub fn impl_field_semigroup_op(&self) -> FieldValue {
+        let Self { member, .. } = self;
+        self.impl_lift_op(
+            parse_quote! { base.#member },
+            parse_quote! { other.#member },
+        )
+    }
+    pub fn impl_variant_field_semigroup_op(&self) -> FieldValue {
+        let (base, other) = (
+            binding_ident("base", &self.member),
+            binding_ident("other", &self.member),
+        );
+        self.impl_lift_op(parse_quote! { #base }, parse_quote! { #other })
+    }
+    pub fn impl_lift_op(&self, base: Expr, other: Expr) -> FieldValue {
         let Self {
             constant:
                 Constant {
@@ -62,15 +166,112 @@ impl<'a> FieldSemigroupOp<'a> {
             field_attr,
             ..
         } = self;
+        if field_attr.is_skip() {
+            return parse_quote! { #member: #base };
+        } else if let Some(op) = field_attr.op() {
+            return parse_quote! { #member: #op(#base, #other) };
+        }
         let with = field_attr.with(container_attr);
         with.map(|path| {
             parse_quote! {
-                #member: <#path<_> as #path_construction_trait<_>>::lift_op(base.#member, other.#member)
+                #member: <#path<_> as #path_construction_trait<_>>::lift_op(#base, #other)
             }
         })
         .unwrap_or_else(|| {
             parse_quote! {
-                #member: #path_semigroup::op(base.#member, other.#member)
+                #member: #path_semigroup::op(#base, #other)
+            }
+        })
+    }
+    pub fn impl_field_try_op(&self) -> Stmt {
+        let Self { member, .. } = self;
+        self.impl_lift_try_op(
+            parse_quote! { base.#member },
+            parse_quote! { other.#member },
+        )
+    }
+    pub fn impl_variant_field_try_op(&self) -> Stmt {
+        let (base, other) = (
+            binding_ident("base", &self.member),
+            binding_ident("other", &self.member),
+        );
+        self.impl_lift_try_op(parse_quote! { #base }, parse_quote! { #other })
+    }
+    /// error of the field is collected with its name as the path, skipped or `op` field never fails
+    pub fn impl_lift_try_op(&self, base: Expr, other: Expr) -> Stmt {
+        let Self {
+            constant:
+                Constant {
+                    path_try_semigroup,
+                    path_try_construction,
+                    ..
+                },
+            container_attr,
+            ty,
+            member,
+            field_attr,
+            ..
+        } = self;
+        let ident = binding_ident("try", member);
+        if field_attr.is_skip() {
+            return parse_quote! { let #ident = Some(#base); };
+        } else if let Some(op) = field_attr.op() {
+            return parse_quote! { let #ident = Some(#op(#base, #other)); };
+        }
+        let field = match member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        };
+        let with = field_attr.with(container_attr);
+        let result: Expr = with
+            .map(|path| {
+                let path = respanned(path, ty.span());
+                let path_try_construction = respanned(path_try_construction, ty.span());
+                parse_quote_spanned! { ty.span() =>
+                    <#path<_> as #path_try_construction<_>>::try_lift_op(#base, #other)
+                }
+            })
+            .unwrap_or_else(|| {
+                let path_try_semigroup = respanned(path_try_semigroup, ty.span());
+                parse_quote_spanned! { ty.span() =>
+                    #path_try_semigroup::try_op(#base, #other)
+                }
+            });
+        parse_quote! { let #ident = errors.collect(#field, #result); }
+    }
+    pub fn impl_field_try_value(&self) -> FieldValue {
+        let Self { member, .. } = self;
+        let ident = binding_ident("try", member);
+        parse_quote! { #member: #ident }
+    }
+    /// field without construction must have the property by itself, such as `Commutative`
+    pub fn impl_property_predicate(&self, property: Property) -> Option<WherePredicate> {
+        let Self {
+            constant,
+            container_attr,
+            ty,
+            field_attr,
+            ..
+        } = self;
+        // skipped field keeps base, that is idempotent by itself
+        let plain = field_attr.with(container_attr).is_none() && !field_attr.is_skip();
+        // span the bound at the field, so that unsatisfied bound points at the offending field
+        let path_property = respanned(property.path(constant), ty.span());
+        plain.then(|| parse_quote_spanned! { ty.span() => #ty: #path_property })
+    }
+    /// field with construction must be operated by construction with the property,
+    /// `assert_construction_{property}` is defined in the assertion of the container
+    pub fn impl_property_assertion(&self, property: Property, value: Expr) -> Option<Stmt> {
+        let Self {
+            container_attr,
+            ty,
+            field_attr,
+            ..
+        } = self;
+        let assert_construction = format_ident!("assert_construction_{}", property.name().0);
+        field_attr.with(container_attr).map(|path| {
+            parse_quote_spanned! { ty.span() =>
+                #assert_construction::<#path<_>, _>(#value);
             }
         })
     }
@@ -87,6 +288,9 @@ impl<'a> FieldSemigroupOp<'a> {
             field_attr,
             ..
         } = self;
+        if let Some(unit) = field_attr.unit() {
+            return parse_quote! { #member: #unit };
+        }
         let with = field_attr.with(container_attr);
         with.map(|path| {
             parse_quote! {
@@ -99,6 +303,41 @@ impl<'a> FieldSemigroupOp<'a> {
             }
         })
     }
+    /// field that mentions lifetimes of the container must be converted into `'static` one,
+    /// and it is bounded only if it also mentions type parameters, otherwise checked in the body
+    pub fn impl_into_owned_predicate(&self) -> Option<WherePredicate> {
+        let Self {
+            constant: Constant {
+                path_into_owned, ..
+            },
+            derive,
+            ty,
+            ..
+        } = self;
+        static_lifetime(derive, ty)
+            .filter(|_| mentions_type_param(derive, ty))
+            .map(|owned| parse_quote! { #ty: #path_into_owned<Owned = #owned> })
+    }
+    pub fn impl_field_into_owned(&self, value: Expr) -> FieldValue {
+        let Self {
+            constant: Constant {
+                path_into_owned, ..
+            },
+            derive,
+            ty,
+            member,
+            ..
+        } = self;
+        match static_lifetime(derive, ty) {
+            Some(_) => {
+                let path_into_owned = respanned(path_into_owned, ty.span());
+                parse_quote_spanned! { ty.span() =>
+                    #member: #path_into_owned::into_owned(#value)
+                }
+            }
+            None => parse_quote! { #member: #value },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -107,6 +346,7 @@ pub struct FieldAnnotatedOp<'a> {
     container_attr: &'a ContainerAttr,
     member: Member,
     field_attr: FieldAttr,
+    nested_annotation: Option<Path>,
 }
 impl<'a> FieldAnnotatedOp<'a> {
     pub fn new(
@@ -114,14 +354,20 @@ impl<'a> FieldAnnotatedOp<'a> {
         _derive: &'a DeriveInput,
         container_attr: &'a ContainerAttr,
         member: Member,
-        field_attr: FieldAttr,
-    ) -> Self {
-        Self {
+        field: &'a Field,
+    ) -> syn::Result<Self> {
+        let field_attr = FieldAttr::new(field)?;
+        let nested_annotation = field_attr
+            .is_nested()
+            .then(|| Self::nested_annotation(&field.ty))
+            .transpose()?;
+        Ok(Self {
             constant,
             container_attr,
             member,
             field_attr,
-        }
+            nested_annotation,
+        })
     }
     pub fn new_fields(
         constant: &'a Constant,
@@ -132,18 +378,43 @@ impl<'a> FieldAnnotatedOp<'a> {
         fields
             .iter()
             .zip(fields.members())
-            .map(|(field, member)| {
-                Ok(Self::new(
-                    constant,
-                    derive,
-                    container_attr,
-                    member,
-                    FieldAttr::new(field)?,
-                ))
-            })
+            .map(|(field, member)| Self::new(constant, derive, container_attr, member, field))
             .collect()
     }
 
+    /// `path::to::Nested<T>` => `path::to::NestedAnnotation`
+    pub fn nested_annotation(ty: &Type) -> syn::Result<Path> {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if !path.segments.is_empty() => {
+                let mut path = path.clone();
+                if let Some(last) = path.segments.last_mut() {
+                    last.ident = StructAnnotate::annotation_ident(&last.ident);
+                    last.arguments = PathArguments::None;
+                }
+                Ok(path)
+            }
+            _ => Err(syn::Error::new_spanned(ty, SemigroupError::NestedNotPath)),
+        }
+    }
+    pub fn is_nested(&self) -> bool {
+        self.nested_annotation.is_some()
+    }
+    /// skipped field of `other` is not used, so it is not bound
+    pub fn other_binding_pat(&self, prefix: &str) -> TokenStream {
+        let Self { member, .. } = self;
+        if self.field_attr.is_skip() {
+            quote! { #member: _ }
+        } else {
+            binding_pat(prefix, member)
+        }
+    }
+    pub fn def_field_annotation_type(&self, a: &Ident) -> Type {
+        match &self.nested_annotation {
+            Some(path) => parse_quote! { #path<#a> },
+            None => parse_quote! { #a },
+        }
+    }
+
     pub fn ident_variable(&self) -> Ident {
         match &self.member {
             Member::Named(ident) => ident.clone(),
@@ -157,38 +428,90 @@ impl<'a> FieldAnnotatedOp<'a> {
             format_ident!("{}_annotation", ident),
         )
     }
+    pub fn member(&self) -> &Member {
+        &self.member
+    }
     pub fn impl_field_annotated_op(&self) -> Stmt {
+        let Self { member, .. } = self;
+        self.impl_lift_annotated_op(
+            (
+                parse_quote! { base_value.#member },
+                parse_quote! { base_annotation.#member },
+            ),
+            (
+                parse_quote! { other_value.#member },
+                parse_quote! { other_annotation.#member },
+            ),
+        )
+    }
+    pub fn impl_variant_field_annotated_op(&self) -> Stmt {
+        let Self { member, .. } = self;
+        let [base_value, base_annotation, other_value, other_annotation] = [
+            "base_value",
+            "base_annotation",
+            "other_value",
+            "other_annotation",
+        ]
+        .map(|prefix| binding_ident(prefix, member));
+        self.impl_lift_annotated_op(
+            (
+                parse_quote! { #base_value },
+                parse_quote! { #base_annotation },
+            ),
+            (
+                parse_quote! { #other_value },
+                parse_quote! { #other_annotation },
+            ),
+        )
+    }
+    pub fn impl_lift_annotated_op(
+        &self,
+        (base_value, base_annotation): (Expr, Expr),
+        (other_value, other_annotation): (Expr, Expr),
+    ) -> Stmt {
         let Self {
             constant,
             container_attr,
-            member,
             field_attr,
+            ..
         } = self;
         let Constant {
             path_annotated_semigroup,
             path_annotated,
             path_construction_annotated,
+            path_provenance,
             ..
         } = constant;
         let (ident_value, ident_annotation) = self.ident_parts();
+        if field_attr.is_skip() {
+            return parse_quote! {
+                let (#ident_value, #ident_annotation) = (#base_value, #base_annotation);
+            };
+        }
         let with = field_attr.with(container_attr);
 
-        with.map(|path| {
-            parse_quote! {
-                let (#ident_value, #ident_annotation) = <#path::<_> as #path_construction_annotated<_, _>>::lift_annotated_op(
-                    #path_annotated::new(base_value.#member, base_annotation.#member),
-                    #path_annotated::new(other_value.#member, other_annotation.#member),
-                ).into_parts();
-            }
-        })
-        .unwrap_or_else(|| {
-            parse_quote! {
-                let (#ident_value, #ident_annotation) = #path_annotated_semigroup::annotated_op(
-                    #path_annotated::new(base_value.#member, base_annotation.#member),
-                    #path_annotated::new(other_value.#member, other_annotation.#member),
-                ).into_parts();
-            }
-        })
+        let (base, other): (Expr, Expr) = (
+            parse_quote! { #path_annotated::new(#base_value, #base_annotation) },
+            parse_quote! { #path_annotated::new(#other_value, #other_annotation) },
+        );
+        // nested field has its own provenance per field
+        let op: Expr = if container_attr.is_provenance() && !self.is_nested() {
+            with.map(|path| parse_quote! { #path_provenance::lift_annotated_op::<_, #path::<_>> })
+                .unwrap_or_else(|| parse_quote! { #path_provenance::annotated_op })
+        } else {
+            with.map(|path| {
+                parse_quote! {
+                    <#path::<_> as #path_construction_annotated<_, _>>::lift_annotated_op
+                }
+            })
+            .unwrap_or_else(|| parse_quote! { #path_annotated_semigroup::annotated_op })
+        };
+        parse_quote! {
+            let (#ident_value, #ident_annotation) = #op(
+                #base,
+                #other,
+            ).into_parts();
+        }
     }
     pub fn impl_field_value(&self) -> FieldValue {
         let Self { member, .. } = self;
@@ -197,6 +520,45 @@ impl<'a> FieldAnnotatedOp<'a> {
             #member: #ident_value
         }
     }
+    pub fn impl_nested_annotate(&self, value: Expr) -> Option<Stmt> {
+        let Self {
+            constant: Constant { path_annotate, .. },
+            ..
+        } = self;
+        let (ident_value, ident_annotation) = self.ident_parts();
+        self.is_nested().then(|| {
+            parse_quote! {
+                let (#ident_value, #ident_annotation) = #path_annotate::annotated(#value, annotation.clone()).into_parts();
+            }
+        })
+    }
+    pub fn impl_field_annotate_value(&self, value: Expr) -> FieldValue {
+        let Self { member, .. } = self;
+        let (ident_value, _ident_annotation) = self.ident_parts();
+        if self.is_nested() {
+            parse_quote! { #member: #ident_value }
+        } else {
+            parse_quote! { #member: #value }
+        }
+    }
+    pub fn impl_field_annotate(&self) -> FieldValue {
+        let Self {
+            constant: Constant {
+                path_provenance, ..
+            },
+            container_attr,
+            member,
+            ..
+        } = self;
+        let (_ident_value, ident_annotation) = self.ident_parts();
+        if self.is_nested() {
+            parse_quote! { #member: #ident_annotation }
+        } else if container_attr.is_provenance() {
+            parse_quote! { #member: #path_provenance::new(annotation.clone()) }
+        } else {
+            parse_quote! { #member: annotation.clone() }
+        }
+    }
     pub fn impl_field_annotation(&self) -> FieldValue {
         let Self { member, .. } = self;
         let (_ident_value, ident_annotation) = self.ident_parts();
@@ -205,3 +567,70 @@ impl<'a> FieldAnnotatedOp<'a> {
         }
     }
 }
+
+/// replace lifetimes of the derive input in `ty` by `'static`, `None` if `ty` does not mention them
+pub fn static_lifetime(derive: &DeriveInput, ty: &Type) -> Option<Type> {
+    struct StaticLifetime<'a> {
+        lifetimes: Vec<&'a Lifetime>,
+        replaced: bool,
+    }
+    impl VisitMut for StaticLifetime<'_> {
+        fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+            if self.lifetimes.contains(&&*lifetime) {
+                *lifetime = parse_quote! { 'static };
+                self.replaced = true;
+            }
+        }
+    }
+    let mut visitor = StaticLifetime {
+        lifetimes: derive.generics.lifetimes().map(|l| &l.lifetime).collect(),
+        replaced: false,
+    };
+    let mut ty = ty.clone();
+    visitor.visit_type_mut(&mut ty);
+    visitor.replaced.then_some(ty)
+}
+
+pub fn mentions_type_param(derive: &DeriveInput, ty: &Type) -> bool {
+    struct TypeParamVisitor<'a> {
+        params: Vec<&'a Ident>,
+        mentioned: bool,
+    }
+    impl<'ast> Visit<'ast> for TypeParamVisitor<'_> {
+        fn visit_path(&mut self, path: &'ast Path) {
+            self.mentioned |= path
+                .segments
+                .first()
+                .is_some_and(|seg| self.params.contains(&&seg.ident));
+            visit::visit_path(self, path);
+        }
+    }
+    let mut visitor = TypeParamVisitor {
+        params: derive.generics.type_params().map(|p| &p.ident).collect(),
+        mentioned: false,
+    };
+    visitor.visit_type(ty);
+    visitor.mentioned
+}
+
+pub fn respanned(tokens: impl ToTokens, span: Span) -> TokenStream {
+    tokens
+        .to_token_stream()
+        .into_iter()
+        .map(|mut t| {
+            t.set_span(span);
+            t
+        })
+        .collect()
+}
+
+pub fn binding_ident(prefix: &str, member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => format_ident!("{}_{}", prefix, ident),
+        Member::Unnamed(index) => format_ident!("{}_{}", prefix, index.index),
+    }
+}
+pub fn binding_pat(prefix: &str, member: &Member) -> TokenStream {
+    let ident = binding_ident(prefix, member);
+    quote! { #member: #ident }
+}
@@ -1,15 +1,18 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, ToTokens};
 use syn::{
-    parse_quote, DataStruct, DeriveInput, FieldValue, Fields, Ident, ItemImpl, ItemStruct, Stmt,
+    parse_quote, DataStruct, DeriveInput, Expr, FieldValue, Fields, Ident, ItemConst, ItemFn,
+    ItemImpl, ItemStruct, Stmt,
 };
 
 use crate::{
     annotation::Annotation,
     constant::Constant,
     semigroup::{
-        ast::field_semigroup::{FieldAnnotatedOp, FieldSemigroupOp},
-        attr::ContainerAttr,
+        ast::field_semigroup::{
+            binding_ident, static_lifetime, FieldAnnotatedOp, FieldSemigroupOp,
+        },
+        attr::{ContainerAttr, Property},
     },
 };
 
@@ -22,9 +25,19 @@ pub struct StructSemigroup<'a> {
 }
 impl ToTokens for StructSemigroup<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.impl_semigroup().to_tokens(tokens);
+        if self.attr.is_try() {
+            self.impl_try_semigroup().to_tokens(tokens);
+        } else {
+            self.impl_semigroup().to_tokens(tokens);
+        }
         self.impl_monoid().iter().for_each(|s| s.to_tokens(tokens));
-        self.impl_commutative()
+        for property in self.attr.properties() {
+            self.impl_property(property).to_tokens(tokens);
+            self.impl_property_assertion(property)
+                .iter()
+                .for_each(|s| s.to_tokens(tokens));
+        }
+        self.impl_into_owned()
             .iter()
             .for_each(|s| s.to_tokens(tokens));
     }
@@ -68,6 +81,48 @@ impl<'a> StructSemigroup<'a> {
             }
         }
     }
+    /// all fields are operated even if some of them fail, so that all errors are collected
+    pub fn impl_try_semigroup(&self) -> ItemImpl {
+        let Self {
+            constant,
+            derive,
+            field_ops,
+            ..
+        } = self;
+        let Constant {
+            path_try_semigroup,
+            path_field_errors,
+            ..
+        } = constant;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let fields_try_op = field_ops.iter().map(|op| op.impl_field_try_op());
+        let idents: Vec<_> = field_ops
+            .iter()
+            .map(|op| binding_ident("try", op.member()))
+            .collect();
+        let fields_value = field_ops.iter().map(|op| op.impl_field_try_value());
+        parse_quote! {
+            #[automatically_derived]
+            impl #impl_generics #path_try_semigroup for #ident #ty_generics #where_clause {
+                type Error = #path_field_errors;
+                fn try_op(base: Self, other: Self) -> Result<Self, Self::Error> {
+                    #[allow(unused_mut)]
+                    let mut errors = #path_field_errors::new();
+                    #(#fields_try_op)*
+                    #[allow(unreachable_patterns)]
+                    match (#(#idents,)*) {
+                        (#(Some(#idents),)*) => Ok(Self {
+                            #(#fields_value),*
+                        }),
+                        _ => Err(errors),
+                    }
+                }
+            }
+        }
+    }
     pub fn impl_monoid(&self) -> Option<ItemImpl> {
         let Self {
             constant,
@@ -119,29 +174,121 @@ impl<'a> StructSemigroup<'a> {
                 })
         })
     }
-    pub fn impl_commutative(&self) -> Option<ItemImpl> {
+    pub fn impl_property(&self, property: Property) -> ItemImpl {
         let Self {
             constant,
             derive,
-            attr,
+            field_ops,
             ..
         } = self;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let path_property = property.path(constant);
+        let mut g = generics.clone();
+        field_ops
+            .iter()
+            .filter_map(|op| op.impl_property_predicate(property))
+            .for_each(|w| g.make_where_clause().predicates.push(w));
+        let (impl_generics, ty_generics, where_clause) = g.split_for_impl();
+        parse_quote! {
+            #[automatically_derived]
+            impl #impl_generics #path_property for #ident #ty_generics #where_clause {}
+        }
+    }
+    pub fn impl_property_assertion(&self, property: Property) -> Option<ItemConst> {
+        let Self {
+            constant,
+            derive,
+            field_ops,
+            ..
+        } = self;
+        let DeriveInput {
+            ident, generics, ..
+        } = derive;
+        let assertions: Vec<_> = field_ops
+            .iter()
+            .filter_map(|op| {
+                let member = op.member();
+                op.impl_property_assertion(property, parse_quote! { &value.#member })
+            })
+            .collect();
+        (!assertions.is_empty()).then(|| {
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let assert_fields = format_ident!("assert_fields_{}", property.name().0);
+            property_assertion(
+                constant,
+                property,
+                parse_quote! {
+                    fn #assert_fields #impl_generics (value: &#ident #ty_generics) #where_clause {
+                        #( #assertions )*
+                    }
+                },
+            )
+        })
+    }
+    pub fn impl_into_owned(&self) -> Option<ItemImpl> {
+        let Self {
+            constant,
+            derive,
+            attr,
+            field_ops,
+        } = self;
         let Constant {
-            path_commutative, ..
+            path_into_owned, ..
         } = constant;
         let DeriveInput {
             ident, generics, ..
         } = derive;
-        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-        attr.is_commutative().then(|| {
+        attr.is_into_owned().then(|| {
+            let mut g = generics.clone();
+            field_ops
+                .iter()
+                .filter_map(|op| op.impl_into_owned_predicate())
+                .for_each(|w| g.make_where_clause().predicates.push(w));
+            let (impl_generics, ty_generics, where_clause) = g.split_for_impl();
+            let owned = static_lifetime(derive, &parse_quote! { #ident #ty_generics })
+                .unwrap_or_else(|| parse_quote! { Self });
+            let fields = field_ops.iter().map(|op| {
+                let member = op.member();
+                op.impl_field_into_owned(parse_quote! { self.#member })
+            });
             parse_quote! {
                 #[automatically_derived]
-                impl #impl_generics #path_commutative for #ident #ty_generics #where_clause {}
+                impl #impl_generics #path_into_owned for #ident #ty_generics #where_clause {
+                    type Owned = #owned;
+                    fn into_owned(self) -> Self::Owned {
+                        #ident {
+                            #( #fields ),*
+                        }
+                    }
+                }
             }
         })
     }
 }
 
+/// assert at compile time that constructions of fields have the property, such as `Commutative`
+pub fn property_assertion(
+    constant: &Constant,
+    property: Property,
+    assert_fields: ItemFn,
+) -> ItemConst {
+    let Constant {
+        path_construction_trait,
+        ..
+    } = constant;
+    let path_property = property.path(constant);
+    let assert_construction = format_ident!("assert_construction_{}", property.name().0);
+    parse_quote! {
+        #[allow(dead_code)]
+        const _: () = {
+            fn #assert_construction<C: #path_construction_trait<T> + #path_property, T>(_: &T) {}
+            #assert_fields
+        };
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StructAnnotate<'a> {
     constant: &'a Constant,
@@ -191,18 +338,21 @@ impl<'a> StructAnnotate<'a> {
             ..
         } = self;
         let a = &annotation.param().ident;
+        let annotation = self
+            .field_ops
+            .iter()
+            .map(|op| op.def_field_annotation_type(a));
         match &data_struct.fields {
             Fields::Named(fields) => {
                 let idents = fields.named.iter().map(|f| &f.ident);
                 parse_quote! {
                     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
                     #vis struct #annotation_ident<#a> {
-                        #( #idents: #a ),*
+                        #( #idents: #annotation ),*
                     }
                 }
             }
-            Fields::Unnamed(fields) => {
-                let annotation = fields.unnamed.iter().map(|_| a);
+            Fields::Unnamed(_) => {
                 parse_quote! {
                     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
                     #vis struct #annotation_ident<#a>( #( #annotation ),* );
@@ -281,19 +431,32 @@ impl<'a> StructAnnotate<'a> {
         let (_, ty_generics, _) = generics.split_for_impl();
         let (impl_generics, annotation_type, where_clause) = annotation.split_for_impl(generics);
         let a = &annotation.param().ident;
-        let fields: Vec<FieldValue> = self
-            .data_struct
-            .fields
-            .members()
-            .map(|m| parse_quote! { #m: annotation.clone() })
+        let fields = self.field_ops.iter().map(|op| op.impl_field_annotate());
+        let nested: Vec<_> = self
+            .field_ops
+            .iter()
+            .filter_map(|op| {
+                let member = op.member();
+                op.impl_nested_annotate(parse_quote! { self.#member })
+            })
             .collect();
+        let value: Expr = if nested.is_empty() {
+            parse_quote! { self }
+        } else {
+            let values = self.field_ops.iter().map(|op| {
+                let member = op.member();
+                op.impl_field_annotate_value(parse_quote! { self.#member })
+            });
+            parse_quote! { #ident { #( #values ),* } }
+        };
         parse_quote! {
             #[automatically_derived]
             impl #impl_generics #path_annotate<#annotation_type> for #ident #ty_generics #where_clause {
                 type Annotation = #a;
                 fn annotated(self, annotation: Self::Annotation) -> #path_annotated<Self, #annotation_type> {
+                    #( #nested )*
                     #path_annotated::new(
-                        self,
+                        #value,
                         #annotation_ident {
                             #( #fields ),*
                         },
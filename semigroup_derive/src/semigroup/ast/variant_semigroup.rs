@@ -0,0 +1,317 @@
+use syn::{
+    parse_quote, punctuated::Punctuated, token::Comma, Arm, DeriveInput, Fields, Ident, Variant,
+    WherePredicate,
+};
+
+use crate::{
+    constant::Constant,
+    error::SemigroupError,
+    name::var_name,
+    semigroup::{
+        ast::field_semigroup::{binding_ident, binding_pat, FieldAnnotatedOp, FieldSemigroupOp},
+        attr::{ContainerAttr, Property, VariantAttr},
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct VariantSemigroupOp<'a> {
+    constant: &'a Constant,
+    variant: &'a Variant,
+    variant_attr: VariantAttr,
+    field_ops: Vec<FieldSemigroupOp<'a>>,
+}
+impl<'a> VariantSemigroupOp<'a> {
+    pub fn new(
+        constant: &'a Constant,
+        derive: &'a DeriveInput,
+        container_attr: &'a ContainerAttr,
+        variant: &'a Variant,
+    ) -> syn::Result<Self> {
+        let variant_attr = VariantAttr::new(variant)?;
+        let unit = variant_attr.is_unit();
+        if unit && !container_attr.is_monoid() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                SemigroupError::OnlyMonoid(var_name!(unit)),
+            ));
+        }
+        if unit && !variant.fields.is_empty() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                SemigroupError::OnlyUnitVariant(var_name!(unit)),
+            ));
+        }
+        let field_ops =
+            FieldSemigroupOp::new_fields(constant, derive, container_attr, &variant.fields)?;
+        Ok(Self {
+            constant,
+            variant,
+            variant_attr,
+            field_ops,
+        })
+    }
+    pub fn new_variants(
+        constant: &'a Constant,
+        derive: &'a DeriveInput,
+        container_attr: &'a ContainerAttr,
+        variants: &'a Punctuated<Variant, Comma>,
+    ) -> syn::Result<Vec<Self>> {
+        variants
+            .iter()
+            .map(|variant| Self::new(constant, derive, container_attr, variant))
+            .collect()
+    }
+
+    pub fn ident(&self) -> &Ident {
+        &self.variant.ident
+    }
+    pub fn is_unit(&self) -> bool {
+        self.variant_attr.is_unit()
+    }
+
+    pub fn impl_variant_semigroup_arm(&self) -> Arm {
+        let Self { field_ops, .. } = self;
+        let ident = self.ident();
+        let base = field_ops.iter().map(|op| binding_pat("base", op.member()));
+        let other = field_ops.iter().map(|op| op.other_binding_pat("other"));
+        let fields_op = field_ops
+            .iter()
+            .map(|op| op.impl_variant_field_semigroup_op());
+        parse_quote! {
+            (Self::#ident { #(#base),* }, Self::#ident { #(#other),* }) => Self::#ident {
+                #(#fields_op),*
+            },
+        }
+    }
+    /// like the derived `TrySemigroup` of structs, all fields are operated so that all errors are collected
+    pub fn impl_variant_try_arm(&self) -> Arm {
+        let Self {
+            constant: Constant {
+                path_field_errors, ..
+            },
+            field_ops,
+            ..
+        } = self;
+        let ident = self.ident();
+        let base = field_ops.iter().map(|op| binding_pat("base", op.member()));
+        let other = field_ops.iter().map(|op| op.other_binding_pat("other"));
+        let fields_try_op = field_ops.iter().map(|op| op.impl_variant_field_try_op());
+        let idents: Vec<_> = field_ops
+            .iter()
+            .map(|op| binding_ident("try", op.member()))
+            .collect();
+        let fields_value = field_ops.iter().map(|op| op.impl_field_try_value());
+        parse_quote! {
+            (Self::#ident { #(#base),* }, Self::#ident { #(#other),* }) => {
+                #[allow(unused_mut)]
+                let mut errors = #path_field_errors::new();
+                #(#fields_try_op)*
+                #[allow(unreachable_patterns)]
+                match (#(#idents,)*) {
+                    (#(Some(#idents),)*) => Ok(Self::#ident {
+                        #(#fields_value),*
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    }
+    pub fn impl_unit_variant_arms(&self) -> [Arm; 2] {
+        let ident = self.ident();
+        [
+            parse_quote! { (Self::#ident {}, other) => other, },
+            parse_quote! { (base, Self::#ident {}) => base, },
+        ]
+    }
+    pub fn impl_property_predicates(
+        &self,
+        property: Property,
+    ) -> impl Iterator<Item = WherePredicate> + '_ {
+        self.field_ops
+            .iter()
+            .filter_map(move |op| op.impl_property_predicate(property))
+    }
+    pub fn impl_property_assertion_arm(
+        &self,
+        property: Property,
+        enum_ident: &Ident,
+    ) -> Option<Arm> {
+        let ident = self.ident();
+        let (bindings, assertions): (Vec<_>, Vec<_>) = self
+            .field_ops
+            .iter()
+            .filter_map(|op| {
+                let binding = binding_ident("value", op.member());
+                op.impl_property_assertion(property, parse_quote! { #binding })
+                    .map(|assertion| (binding_pat("value", op.member()), assertion))
+            })
+            .unzip();
+        (!assertions.is_empty()).then(|| {
+            parse_quote! {
+                #enum_ident::#ident { #( #bindings, )* .. } => {
+                    #( #assertions )*
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantAnnotatedOp<'a> {
+    constant: &'a Constant,
+    variant: &'a Variant,
+    variant_attr: VariantAttr,
+    field_ops: Vec<FieldAnnotatedOp<'a>>,
+}
+impl<'a> VariantAnnotatedOp<'a> {
+    pub fn new(
+        constant: &'a Constant,
+        derive: &'a DeriveInput,
+        container_attr: &'a ContainerAttr,
+        variant: &'a Variant,
+    ) -> syn::Result<Self> {
+        let variant_attr = VariantAttr::new(variant)?;
+        let field_ops =
+            FieldAnnotatedOp::new_fields(constant, derive, container_attr, &variant.fields)?;
+        Ok(Self {
+            constant,
+            variant,
+            variant_attr,
+            field_ops,
+        })
+    }
+    pub fn new_variants(
+        constant: &'a Constant,
+        derive: &'a DeriveInput,
+        container_attr: &'a ContainerAttr,
+        variants: &'a Punctuated<Variant, Comma>,
+    ) -> syn::Result<Vec<Self>> {
+        variants
+            .iter()
+            .map(|variant| Self::new(constant, derive, container_attr, variant))
+            .collect()
+    }
+
+    pub fn ident(&self) -> &Ident {
+        &self.variant.ident
+    }
+    pub fn is_unit(&self) -> bool {
+        self.variant_attr.is_unit()
+    }
+
+    pub fn has_nested(&self) -> bool {
+        self.field_ops.iter().any(|op| op.is_nested())
+    }
+
+    pub fn def_annotation_variant(&self, a: &Ident) -> Variant {
+        let ident = self.ident();
+        let annotation = self
+            .field_ops
+            .iter()
+            .map(|op| op.def_field_annotation_type(a));
+        match &self.variant.fields {
+            Fields::Named(fields) => {
+                let idents = fields.named.iter().map(|f| &f.ident);
+                parse_quote! { #ident { #( #idents: #annotation ),* } }
+            }
+            Fields::Unnamed(_) => parse_quote! { #ident( #( #annotation ),* ) },
+            Fields::Unit => parse_quote! { #ident },
+        }
+    }
+    pub fn impl_annotate_arm(&self, annotation_ident: &Ident) -> Arm {
+        let ident = self.ident();
+        let fields = self.field_ops.iter().map(|op| op.impl_field_annotate());
+        parse_quote! {
+            Self::#ident { .. } => #annotation_ident::#ident { #( #fields ),* },
+        }
+    }
+    pub fn impl_nested_annotate_arm(&self, annotation_ident: &Ident) -> Arm {
+        let Self {
+            constant: Constant { path_annotated, .. },
+            field_ops,
+            ..
+        } = self;
+        let ident = self.ident();
+        let bindings = field_ops.iter().map(|op| binding_pat("self", op.member()));
+        let nested = field_ops.iter().filter_map(|op| {
+            let binding = binding_ident("self", op.member());
+            op.impl_nested_annotate(parse_quote! { #binding })
+        });
+        let values = field_ops.iter().map(|op| {
+            let binding = binding_ident("self", op.member());
+            op.impl_field_annotate_value(parse_quote! { #binding })
+        });
+        let fields = field_ops.iter().map(|op| op.impl_field_annotate());
+        parse_quote! {
+            Self::#ident { #( #bindings ),* } => {
+                #( #nested )*
+                #path_annotated::new(
+                    Self::#ident { #( #values ),* },
+                    #annotation_ident::#ident { #( #fields ),* },
+                )
+            }
+        }
+    }
+    pub fn impl_variant_annotated_arm(&self, annotation_ident: &Ident) -> Arm {
+        let Self {
+            constant: Constant { path_annotated, .. },
+            field_ops,
+            ..
+        } = self;
+        let ident = self.ident();
+        let [base_value, base_annotation] = ["base_value", "base_annotation"].map(|prefix| {
+            field_ops
+                .iter()
+                .map(|op| binding_pat(prefix, op.member()))
+                .collect::<Vec<_>>()
+        });
+        let [other_value, other_annotation] = ["other_value", "other_annotation"].map(|prefix| {
+            field_ops
+                .iter()
+                .map(|op| op.other_binding_pat(prefix))
+                .collect::<Vec<_>>()
+        });
+        let local = field_ops
+            .iter()
+            .map(|op| op.impl_variant_field_annotated_op());
+        let value = field_ops.iter().map(|op| op.impl_field_value());
+        let field_annotation = field_ops.iter().map(|op| op.impl_field_annotation());
+        parse_quote! {
+            (
+                Self::#ident { #(#base_value),* },
+                #annotation_ident::#ident { #(#base_annotation),* },
+                Self::#ident { #(#other_value),* },
+                #annotation_ident::#ident { #(#other_annotation),* },
+            ) => {
+                #( #local )*
+                #path_annotated::new(
+                    Self::#ident {
+                        #( #value ),*
+                    },
+                    #annotation_ident::#ident {
+                        #( #field_annotation ),*
+                    },
+                )
+            }
+        }
+    }
+    pub fn impl_unit_variant_arms(&self, annotation_ident: &Ident) -> [Arm; 2] {
+        let Self {
+            constant: Constant { path_annotated, .. },
+            ..
+        } = self;
+        let ident = self.ident();
+        [
+            parse_quote! {
+                (Self::#ident {}, #annotation_ident::#ident {}, other_value, other_annotation) => {
+                    #path_annotated::new(other_value, other_annotation)
+                }
+            },
+            parse_quote! {
+                (base_value, base_annotation, Self::#ident {}, #annotation_ident::#ident {}) => {
+                    #path_annotated::new(base_value, base_annotation)
+                }
+            },
+        ]
+    }
+}
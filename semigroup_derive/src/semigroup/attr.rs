@@ -1,13 +1,20 @@
-use darling::{FromDeriveInput, FromField};
-use syn::{parse_quote, DeriveInput, Expr, Field, Ident, Path, WherePredicate};
+use darling::{FromDeriveInput, FromField, FromMeta, FromVariant};
+use syn::{parse_quote, DeriveInput, Expr, Field, Ident, Path, Variant, WherePredicate};
 
-use crate::{annotation::Annotation, constant::Constant, error::SemigroupError, name::var_name};
+use crate::{
+    annotation::Annotation,
+    constant::Constant,
+    error::SemigroupError,
+    name::{var_name, Name},
+};
 
 #[derive(Debug, Clone, PartialEq, FromDeriveInput)]
 #[darling(attributes(semigroup), and_then = Self::validate)]
 pub struct ContainerAttr {
     #[darling(default)]
     annotated: bool,
+    #[darling(default)]
+    provenance: bool,
 
     #[darling(default)]
     monoid: bool,
@@ -18,9 +25,19 @@ pub struct ContainerAttr {
 
     #[darling(default)]
     commutative: bool,
+    #[darling(default)]
+    idempotent: bool,
+
+    #[darling(default)]
+    into_owned: bool,
+
+    #[darling(default, rename = "try")]
+    fallible: bool,
 
     with: Option<Path>,
     annotation_param: Option<Ident>,
+
+    mismatch: Option<Mismatch>,
 }
 impl ContainerAttr {
     pub fn new(derive: &DeriveInput) -> syn::Result<Self> {
@@ -30,15 +47,22 @@ impl ContainerAttr {
         let Self {
             annotated,
             annotation_param,
+            provenance,
             monoid,
             unit,
             unit_where,
             without_monoid_impl,
+            commutative,
+            idempotent,
+            fallible,
+            mismatch,
             ..
         } = &self;
         if !annotated {
             let err_attr_name = if annotation_param.is_some() {
                 Some(var_name!(annotation_param))
+            } else if *provenance {
+                Some(var_name!(provenance))
             } else {
                 None
             };
@@ -60,12 +84,40 @@ impl ContainerAttr {
                 Err(darling::Error::custom(SemigroupError::OnlyMonoid(a)))
             })?;
         }
+        // a mismatch can be reported only as the error of `TrySemigroup`
+        if !fallible && *mismatch == Some(Mismatch::Error) {
+            return Err(darling::Error::custom(SemigroupError::OnlyTry(Name(
+                r#"mismatch = "error""#,
+            ))));
+        }
+        if *fallible {
+            let err_attr_name = if *annotated {
+                Some(var_name!(annotated))
+            } else if *monoid {
+                Some(var_name!(monoid))
+            } else if *commutative {
+                Some(var_name!(commutative))
+            } else if *idempotent {
+                Some(var_name!(idempotent))
+            } else {
+                None
+            };
+            err_attr_name.map_or(Ok(()), |a| {
+                Err(darling::Error::custom(SemigroupError::Conflict(
+                    a,
+                    Name("try"),
+                )))
+            })?;
+        }
         Ok(self)
     }
 
     pub fn is_annotated(&self) -> bool {
         self.annotated
     }
+    pub fn is_provenance(&self) -> bool {
+        self.provenance
+    }
 
     pub fn is_monoid(&self) -> bool {
         self.monoid
@@ -86,31 +138,163 @@ impl ContainerAttr {
     pub fn is_commutative(&self) -> bool {
         self.commutative
     }
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+    pub fn properties(&self) -> impl Iterator<Item = Property> {
+        [
+            (self.commutative, Property::Commutative),
+            (self.idempotent, Property::Idempotent),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, property)| enabled.then_some(property))
+    }
+
+    pub fn is_into_owned(&self) -> bool {
+        self.into_owned
+    }
+
+    pub fn is_try(&self) -> bool {
+        self.fallible
+    }
+
+    pub fn mismatch(&self) -> Option<Mismatch> {
+        self.mismatch
+    }
 
     pub fn annotation(&self, constant: &Constant, annotation_ident: &Ident) -> Annotation {
         let a = self
             .annotation_param
             .as_ref()
             .unwrap_or(&constant.default_type_param.ident);
-        Annotation::new(
-            parse_quote! { #a: Clone },
-            Some(parse_quote! { #annotation_ident<#a> }),
-            None,
-        )
+        let Constant {
+            path_provenance, ..
+        } = constant;
+        let ty = if self.is_provenance() {
+            parse_quote! { #annotation_ident<#path_provenance<#a>> }
+        } else {
+            parse_quote! { #annotation_ident<#a> }
+        };
+        Annotation::new(parse_quote! { #a: Clone }, Some(ty), None)
     }
 }
 
-#[derive(Debug, Clone, FromField)]
+/// marker trait implemented by the derive, that requires its fields to have the same property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Property {
+    Commutative,
+    Idempotent,
+}
+impl Property {
+    pub fn name(&self) -> Name {
+        match self {
+            Self::Commutative => Name("commutative"),
+            Self::Idempotent => Name("idempotent"),
+        }
+    }
+    pub fn path<'a>(&self, constant: &'a Constant) -> &'a Path {
+        match self {
+            Self::Commutative => &constant.path_commutative,
+            Self::Idempotent => &constant.path_idempotent,
+        }
+    }
+}
+
+/// how different variants of an enum are operated,
+/// keeping `base` or `other` is not offered because it is not associative
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub enum Mismatch {
+    Priority,
+    Error,
+}
+
+#[derive(Debug, Clone, FromVariant)]
 #[darling(attributes(semigroup))]
+pub struct VariantAttr {
+    #[darling(default)]
+    unit: bool,
+}
+impl VariantAttr {
+    pub fn new(variant: &Variant) -> syn::Result<Self> {
+        Ok(Self::from_variant(variant)?)
+    }
+    pub fn is_unit(&self) -> bool {
+        self.unit
+    }
+}
+
+#[derive(Debug, Clone, FromField)]
+#[darling(attributes(semigroup), and_then = Self::validate)]
 pub struct FieldAttr {
     with: Option<Path>,
+    #[darling(default)]
+    nested: bool,
+    #[darling(default)]
+    skip: bool,
+    op: Option<Path>,
+    unit: Option<Expr>,
 }
 impl FieldAttr {
     pub fn new(field: &Field) -> syn::Result<Self> {
         Ok(Self::from_field(field)?)
     }
+    pub fn validate(self) -> darling::Result<Self> {
+        let Self {
+            with,
+            nested,
+            skip,
+            op,
+            unit,
+        } = &self;
+        let conflict = if *nested {
+            let other = if with.is_some() {
+                Some(var_name!(with))
+            } else if *skip {
+                Some(var_name!(skip))
+            } else if op.is_some() {
+                Some(var_name!(op))
+            } else if unit.is_some() {
+                Some(var_name!(unit))
+            } else {
+                None
+            };
+            other.map(|o| (o, var_name!(nested)))
+        } else if *skip {
+            let other = if with.is_some() {
+                Some(var_name!(with))
+            } else if op.is_some() {
+                Some(var_name!(op))
+            } else {
+                None
+            };
+            other.map(|o| (o, var_name!(skip)))
+        } else if op.is_some() && with.is_some() {
+            Some((var_name!(with), var_name!(op)))
+        } else {
+            None
+        };
+        conflict.map_or(Ok(self), |(a, b)| {
+            Err(darling::Error::custom(SemigroupError::Conflict(a, b)))
+        })
+    }
     pub fn with<'a>(&'a self, container: &'a ContainerAttr) -> Option<&'a Path> {
-        self.with.as_ref().or(container.with.as_ref())
+        if self.nested || self.skip || self.op.is_some() {
+            None
+        } else {
+            self.with.as_ref().or(container.with.as_ref())
+        }
+    }
+    pub fn is_nested(&self) -> bool {
+        self.nested
+    }
+    pub fn is_skip(&self) -> bool {
+        self.skip
+    }
+    pub fn op(&self) -> Option<&Path> {
+        self.op.as_ref()
+    }
+    pub fn unit(&self) -> Option<&Expr> {
+        self.unit.as_ref()
     }
 }
 
@@ -148,6 +332,14 @@ mod tests {
         },
         Err("attribute `annotation_param` are supported only with `annotated`"),
     )]
+    #[case::invalid_provenance_attr(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(provenance)]
+            pub struct NamedStruct {}
+        },
+        Err("attribute `provenance` are supported only with `annotated`"),
+    )]
     #[case::invalid_monoid_attr(
         syn::parse_quote! {
             #[derive(Semigroup)]
@@ -156,6 +348,97 @@ mod tests {
         },
         Err("attribute `unit` are supported only with `monoid`"),
     )]
+    #[case::mismatch(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(mismatch = "priority")]
+            pub enum Enum {}
+        },
+        Ok(ContainerAttr {
+            mismatch: Some(Mismatch::Priority),
+            ..default_container_attr()
+        }),
+    )]
+    #[case::invalid_mismatch(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(mismatch = "base")]
+            pub enum Enum {}
+        },
+        Err("Unknown value: `base`. Available values: `error`, `priority`"),
+    )]
+    #[case::mismatch_error(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(try, mismatch = "error")]
+            pub enum Enum {}
+        },
+        Ok(ContainerAttr {
+            fallible: true,
+            mismatch: Some(Mismatch::Error),
+            ..default_container_attr()
+        }),
+    )]
+    #[case::invalid_mismatch_error(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(mismatch = "error")]
+            pub enum Enum {}
+        },
+        Err(r#"attribute `mismatch = "error"` are supported only with `try`"#),
+    )]
+    #[case::try_mode(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(try, with = "semigroup::op::Unique")]
+            pub struct NamedStruct {}
+        },
+        Ok(ContainerAttr {
+            fallible: true,
+            with: Some(parse_quote! { semigroup::op::Unique }),
+            ..default_container_attr()
+        }),
+    )]
+    #[case::invalid_try_monoid(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(try, monoid)]
+            pub struct NamedStruct {}
+        },
+        Err("attribute `monoid` are not supported with `try`"),
+    )]
+    #[case::commutative_mismatch(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(commutative, mismatch = "priority")]
+            pub enum Enum {}
+        },
+        Ok(ContainerAttr {
+            commutative: true,
+            mismatch: Some(Mismatch::Priority),
+            ..default_container_attr()
+        }),
+    )]
+    #[case::idempotent(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(commutative, idempotent)]
+            pub struct NamedStruct {}
+        },
+        Ok(ContainerAttr {
+            commutative: true,
+            idempotent: true,
+            ..default_container_attr()
+        }),
+    )]
+    #[case::invalid_try_idempotent(
+        syn::parse_quote! {
+            #[derive(Semigroup)]
+            #[semigroup(try, idempotent)]
+            pub struct NamedStruct {}
+        },
+        Err("attribute `idempotent` are not supported with `try`"),
+    )]
     fn test_semigroup_container_attr(
         #[case] input: DeriveInput,
         #[case] expected: Result<ContainerAttr, &str>,
@@ -166,4 +449,54 @@ mod tests {
             expected.as_ref().map_err(ToString::to_string),
         );
     }
+
+    #[rstest]
+    #[case::nested(
+        syn::parse_quote! { #[semigroup(nested)] server: Server },
+        Ok(true),
+    )]
+    #[case::invalid_nested_with(
+        syn::parse_quote! { #[semigroup(nested, with = "semigroup::op::Coalesce")] server: Server },
+        Err("attribute `with` are not supported with `nested`"),
+    )]
+    #[case::skip(
+        syn::parse_quote! { #[semigroup(skip)] handle: Handle },
+        Ok(false),
+    )]
+    #[case::op_with_unit(
+        syn::parse_quote! { #[semigroup(op = "std::cmp::max", unit = 0)] count: u32 },
+        Ok(false),
+    )]
+    #[case::invalid_nested_unit(
+        syn::parse_quote! { #[semigroup(nested, unit = Server::default())] server: Server },
+        Err("attribute `unit` are not supported with `nested`"),
+    )]
+    #[case::invalid_skip_with(
+        syn::parse_quote! { #[semigroup(skip, with = "semigroup::op::Coalesce")] handle: Handle },
+        Err("attribute `with` are not supported with `skip`"),
+    )]
+    #[case::invalid_skip_op(
+        syn::parse_quote! { #[semigroup(skip, op = "std::cmp::max")] handle: Handle },
+        Err("attribute `op` are not supported with `skip`"),
+    )]
+    #[case::invalid_op_with(
+        syn::parse_quote! { #[semigroup(op = "std::cmp::max", with = "semigroup::op::Max")] count: u32 },
+        Err("attribute `with` are not supported with `op`"),
+    )]
+    fn test_semigroup_field_attr(#[case] input: FieldInput, #[case] expected: Result<bool, &str>) {
+        let actual = FieldAttr::new(&input.0);
+        assert_eq!(
+            actual
+                .as_ref()
+                .map(FieldAttr::is_nested)
+                .map_err(ToString::to_string),
+            expected.map_err(ToString::to_string),
+        );
+    }
+    struct FieldInput(Field);
+    impl syn::parse::Parse for FieldInput {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            Field::parse_named(input).map(Self)
+        }
+    }
 }